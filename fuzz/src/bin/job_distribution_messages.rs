@@ -0,0 +1,19 @@
+use honggfuzz::fuzz;
+use stratumv2::{
+    codec::{deserialize, Deserializable},
+    job_distribution,
+};
+
+fn main() {
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_distribution::SetupConnection>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_distribution::SetupConnectionSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_distribution::SetupConnectionError>(&data);
+    });
+}
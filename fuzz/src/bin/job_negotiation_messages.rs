@@ -0,0 +1,55 @@
+use honggfuzz::fuzz;
+use stratumv2::{
+    codec::{deserialize, Deserializable},
+    job_negotiation,
+};
+
+fn main() {
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::SetupConnection>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::SetupConnectionSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::SetupConnectionError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::AllocateMiningJobToken>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::AllocateMiningJobTokenSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::CommitMiningJob>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::CommitMiningJobSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::CommitMiningJobError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::IdentifyTransactions>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::IdentifyTransactionsSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::ProvideMissingTransactions>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<job_negotiation::ProvideMissingTransactionsSuccess>(&data);
+    });
+}
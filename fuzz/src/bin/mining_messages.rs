@@ -28,4 +28,68 @@ fn main() {
     fuzz!(|data: &[u8]| {
         deserialize::<mining::UpdateChannel>(&data);
     });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::UpdateChannelError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::CloseChannel>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetExtranoncePrefix>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SubmitSharesStandard>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SubmitSharesExtended>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SubmitSharesSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SubmitSharesError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::NewMiningJob>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::NewExtendedMiningJob>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetNewPrevHash>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetTarget>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetCustomMiningJob>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetCustomMiningJobSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetCustomMiningJobError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::Reconnect>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<mining::SetGroupChannel>(&data);
+    });
 }
@@ -1,8 +1,18 @@
 use honggfuzz::fuzz;
-use stratumv2::codec::{deserialize, Deserializable, Message};
+use stratumv2::codec::{deserialize, AnyMessage, Deserializable, FrameDecoder, Message};
 
 fn main() {
     fuzz!(|data: &[u8]| {
         deserialize::<Message>(&data);
     });
+
+    fuzz!(|data: &[u8]| {
+        FrameDecoder::new().decode_all(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        if let Ok(message) = deserialize::<Message>(&data) {
+            AnyMessage::decode(&message);
+        }
+    });
 }
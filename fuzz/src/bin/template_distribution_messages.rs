@@ -0,0 +1,47 @@
+use honggfuzz::fuzz;
+use stratumv2::{
+    codec::{deserialize, Deserializable},
+    template_distribution,
+};
+
+fn main() {
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::SetupConnection>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::SetupConnectionSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::SetupConnectionError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::CoinbaseOutputDataSize>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::NewTemplate>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::SetNewPrevHash>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::RequestTransactionData>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::RequestTransactionDataSuccess>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::RequestTransactionDataError>(&data);
+    });
+
+    fuzz!(|data: &[u8]| {
+        deserialize::<template_distribution::SubmitSolution>(&data);
+    });
+}
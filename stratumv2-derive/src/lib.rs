@@ -0,0 +1,283 @@
+//! Derive macros for the Serializable, Deserializable and Frameable traits of
+//! the [stratumv2](https://docs.rs/stratumv2) crate.
+//!
+//! The macros are re-exported from `stratumv2::codec` and allow downstream
+//! crates to define Stratum V2 messages, for example messages of an extension,
+//! without depending on the internal message macros.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use stratumv2::codec::{deserialize, frame, serialize, Deserializable, Frameable, Serializable};
+//! use stratumv2::types::{MessageType, STR0_255};
+//!
+//! #[derive(Debug, PartialEq, Serializable, Deserializable, Frameable)]
+//! #[sv2(message_type = TestMessage2, channel_bit = true)]
+//! struct Ping {
+//!     channel_id: u32,
+//!     greeting: STR0_255,
+//! }
+//!
+//! let ping = Ping {
+//!     channel_id: 1,
+//!     greeting: STR0_255::new("hello").unwrap(),
+//! };
+//!
+//! let serialized = serialize(&ping).unwrap();
+//! assert_eq!(deserialize::<Ping>(&serialized).unwrap(), ping);
+//! assert_eq!(frame(&ping).unwrap().message_type, MessageType::TestMessage2);
+//! ```
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Ident, LitBool, LitInt};
+
+/// Derives `stratumv2::codec::Serializable` by serializing each field in
+/// declaration order.
+#[proc_macro_derive(Serializable)]
+pub fn derive_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_serializable(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `stratumv2::codec::Deserializable` by deserializing each field in
+/// declaration order. The struct is built directly from the fields, so no
+/// constructor is required. Errors are wrapped in a `FieldError` with the name
+/// of the field, or its index for tuple structs.
+#[proc_macro_derive(Deserializable)]
+pub fn derive_deserializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_deserializable(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `stratumv2::codec::Frameable`.
+///
+/// The MessageType defaults to the variant with the same name as the struct
+/// and can be set explicitly using `#[sv2(message_type = Variant)]`. Adding
+/// `channel_bit = true` or `channel_bit = false` verifies at compile time
+/// that the channel bit of the MessageType matches.
+///
+/// Messages of a protocol extension set the extension_type and msg_type of a
+/// `MessageType::Extension` using
+/// `#[sv2(extension_type = 0x0001, message_type = 0x00, channel_bit = true)]`.
+/// The channel bit of an extension message defaults to false.
+#[proc_macro_derive(Frameable, attributes(sv2))]
+pub fn derive_frameable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_frameable(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The member used to access each field of a struct, either by name or by
+/// index for tuple structs.
+fn field_members(input: &DeriveInput) -> syn::Result<Vec<(syn::Member, &syn::Type)>> {
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "stratumv2 messages can only be derived for structs",
+            ))
+        }
+    };
+
+    Ok(data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(i.into()),
+            };
+            (member, &field.ty)
+        })
+        .collect())
+}
+
+fn expand_serializable(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members: Vec<syn::Member> = field_members(input)?
+        .into_iter()
+        .map(|(member, _)| member)
+        .collect();
+
+    Ok(quote! {
+        impl #impl_generics ::stratumv2::codec::Serializable for #name #ty_generics #where_clause {
+            fn serialize<W: ::stratumv2::codec::io::Write>(
+                &self,
+                writer: &mut W,
+            ) -> ::stratumv2::error::Result<usize> {
+                #[allow(unused_mut)]
+                let mut length = 0usize;
+                #(
+                    length += ::stratumv2::codec::Serializable::serialize(&self.#members, writer)?;
+                )*
+                Ok(length)
+            }
+
+            fn encoded_len(&self) -> usize {
+                0usize #(+ ::stratumv2::codec::Serializable::encoded_len(&self.#members))*
+            }
+        }
+    })
+}
+
+fn expand_deserializable(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = field_members(input)?;
+
+    // Fields are deserialized into locals first so that they are read in
+    // declaration order regardless of the struct literal syntax.
+    let locals: Vec<Ident> = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let types = fields.iter().map(|(_, ty)| ty);
+    let names = fields.iter().map(|(member, _)| match member {
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    });
+    let members = fields.iter().map(|(member, _)| member);
+
+    Ok(quote! {
+        impl #impl_generics ::stratumv2::codec::Deserializable for #name #ty_generics #where_clause {
+            fn deserialize(
+                parser: &mut ::stratumv2::codec::ByteParser,
+            ) -> ::stratumv2::error::Result<Self> {
+                #(
+                    let #locals = ::stratumv2::codec::deserialize_field::<#types>(parser, #names)?;
+                )*
+                Ok(#name { #(#members: #locals),* })
+            }
+        }
+    })
+}
+
+/// The value of the `message_type` attribute, either the name of a
+/// MessageType variant or the msg_type of an extension message.
+enum MessageTypeAttribute {
+    Variant(Ident),
+    Extension(LitInt),
+}
+
+/// The options set by the `#[sv2(...)]` attributes of a struct.
+struct Sv2Attributes {
+    extension_type: Option<LitInt>,
+    message_type: Option<MessageTypeAttribute>,
+    channel_bit: Option<LitBool>,
+}
+
+fn parse_sv2_attributes(input: &DeriveInput) -> syn::Result<Sv2Attributes> {
+    let mut attributes = Sv2Attributes {
+        extension_type: None,
+        message_type: None,
+        channel_bit: None,
+    };
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("sv2"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("extension_type") {
+                attributes.extension_type = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("message_type") {
+                let value = meta.value()?;
+                attributes.message_type = Some(if value.peek(LitInt) {
+                    MessageTypeAttribute::Extension(value.parse()?)
+                } else {
+                    MessageTypeAttribute::Variant(value.parse()?)
+                });
+                Ok(())
+            } else if meta.path.is_ident("channel_bit") {
+                attributes.channel_bit = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `extension_type`, `message_type` or `channel_bit`"))
+            }
+        })?;
+    }
+
+    Ok(attributes)
+}
+
+fn expand_frameable(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attributes = parse_sv2_attributes(input)?;
+
+    let (message_type, checks) = match (attributes.extension_type, attributes.message_type) {
+        (Some(ext_type), Some(MessageTypeAttribute::Extension(msg_type))) => {
+            let channel_bit = attributes
+                .channel_bit
+                .map(|channel_bit| channel_bit.value)
+                .unwrap_or(false);
+            let message_type = quote! {
+                ::stratumv2::types::MessageType::Extension {
+                    ext_type: #ext_type,
+                    msg_type: #msg_type,
+                    channel_bit: #channel_bit,
+                }
+            };
+            let checks = quote! {
+                const _: () = assert!(
+                    #message_type.ext_type() != 0 && #message_type.ext_type() <= 0x7fff,
+                    "the extension_type must be between 1 and 0x7fff"
+                );
+            };
+            (message_type, checks)
+        }
+        (Some(ext_type), _) => {
+            return Err(syn::Error::new_spanned(
+                ext_type,
+                "extension messages require an integer `message_type`",
+            ))
+        }
+        (None, Some(MessageTypeAttribute::Extension(msg_type))) => {
+            return Err(syn::Error::new_spanned(
+                msg_type,
+                "an integer `message_type` requires an `extension_type`",
+            ))
+        }
+        (None, message_type) => {
+            let variant = match message_type {
+                Some(MessageTypeAttribute::Variant(variant)) => variant,
+                _ => name.clone(),
+            };
+            let message_type = quote! { ::stratumv2::types::MessageType::#variant };
+            let checks = attributes.channel_bit.map(|channel_bit| {
+                let error = format!(
+                    "the channel bit of MessageType::{} is not {}",
+                    variant,
+                    channel_bit.value()
+                );
+                quote! {
+                    const _: () = assert!(#message_type.channel_bit() == #channel_bit, #error);
+                }
+            });
+            (message_type, quote! { #checks })
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::stratumv2::codec::Frameable for #name #ty_generics #where_clause {
+            fn message_type() -> ::stratumv2::types::MessageType {
+                #message_type
+            }
+        }
+
+        #checks
+    })
+}
@@ -0,0 +1,244 @@
+use crate::codec::{frame, unframe, Message};
+use crate::error::{Error, Result};
+use crate::types::MessageType;
+use crate::{common, job_negotiation, mining, template_distribution};
+
+/// Internal macro to generate the AnyMessage enum from each MessageType
+/// variant and the message that is framed with that MessageType.
+macro_rules! impl_any_message {
+    ($($variant:ident => $message:ty),*) => {
+        /// AnyMessage contains a variant for every message implemented by the
+        /// library, allowing a received [Message](struct.Message.html) to be
+        /// decoded without knowing its type and handled using `match`.
+        ///
+        /// Each variant has the name of the MessageType of the message.
+        /// SetupConnectionSuccess and SetupConnectionError are decoded into
+        /// the common messages, since their flags depend on the sub protocol
+        /// of the connection. Frames of protocol extensions are kept as the
+        /// undecoded Message.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// use stratumv2::codec::{frame, AnyMessage};
+        /// use stratumv2::mining::CloseChannel;
+        ///
+        /// let message = frame(&CloseChannel::new(1, "shutdown").unwrap()).unwrap();
+        ///
+        /// match AnyMessage::decode(&message).unwrap() {
+        ///     AnyMessage::CloseChannel(close_channel) => assert_eq!(close_channel.channel_id, 1),
+        ///     _ => panic!("unexpected message"),
+        /// }
+        /// ```
+        #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum AnyMessage {
+            $($variant($message),)*
+            Extension(Message),
+        }
+
+        impl AnyMessage {
+            /// Decodes the payload of a network frame into the message of its
+            /// MessageType.
+            pub fn decode(message: &Message) -> Result<AnyMessage> {
+                match message.message_type {
+                    $(MessageType::$variant => Ok(AnyMessage::$variant(unframe(message)?)),)*
+                    MessageType::Extension { .. } => Ok(AnyMessage::Extension(message.clone())),
+                    _ => Err(Error::UnknownMessageType()),
+                }
+            }
+
+            /// Encodes the message into a network frame.
+            pub fn encode(&self) -> Result<Message> {
+                match self {
+                    $(AnyMessage::$variant(m) => frame(m),)*
+                    AnyMessage::Extension(m) => Ok(m.clone()),
+                }
+            }
+
+            /// Returns the MessageType of the message.
+            pub fn message_type(&self) -> MessageType {
+                match self {
+                    $(AnyMessage::$variant(_) => MessageType::$variant,)*
+                    AnyMessage::Extension(m) => m.message_type,
+                }
+            }
+        }
+
+        $(
+            impl From<$message> for AnyMessage {
+                fn from(m: $message) -> Self {
+                    AnyMessage::$variant(m)
+                }
+            }
+        )*
+    };
+}
+
+impl_any_message!(
+    SetupConnection => common::SetupConnection,
+    SetupConnectionSuccess => common::SetupConnectionSuccess,
+    SetupConnectionError => common::SetupConnectionError,
+    ChannelEndpointChanged => common::ChannelEndpointChanged,
+    OpenStandardMiningChannel => mining::OpenStandardMiningChannel,
+    OpenStandardMiningChannelSuccess => mining::OpenStandardMiningChannelSuccess,
+    OpenStandardMiningChannelError => mining::OpenStandardMiningChannelError,
+    OpenExtendedMiningChannel => mining::OpenExtendedMiningChannel,
+    OpenExtendedMiningChannelSuccess => mining::OpenExtendedMiningChannelSuccess,
+    OpenExtendedMiningChannelError => mining::OpenExtendedMiningChannelError,
+    UpdateChannel => mining::UpdateChannel,
+    UpdateChannelError => mining::UpdateChannelError,
+    CloseChannel => mining::CloseChannel,
+    SetExtranoncePrefix => mining::SetExtranoncePrefix,
+    SubmitSharesStandard => mining::SubmitSharesStandard,
+    SubmitSharesExtended => mining::SubmitSharesExtended,
+    SubmitSharesSuccess => mining::SubmitSharesSuccess,
+    SubmitSharesError => mining::SubmitSharesError,
+    NewMiningJob => mining::NewMiningJob,
+    NewExtendedMiningJob => mining::NewExtendedMiningJob,
+    SetNewPrevHash => mining::SetNewPrevHash,
+    SetTarget => mining::SetTarget,
+    SetCustomMiningJob => mining::SetCustomMiningJob,
+    SetCustomMiningJobSuccess => mining::SetCustomMiningJobSuccess,
+    SetCustomMiningJobError => mining::SetCustomMiningJobError,
+    Reconnect => mining::Reconnect,
+    SetGroupChannel => mining::SetGroupChannel,
+    AllocateMiningJobToken => job_negotiation::AllocateMiningJobToken,
+    AllocateMiningJobTokenSuccess => job_negotiation::AllocateMiningJobTokenSuccess,
+    IdentifyTransactions => job_negotiation::IdentifyTransactions,
+    IdentifyTransactionsSuccess => job_negotiation::IdentifyTransactionsSuccess,
+    ProvideMissingTransactions => job_negotiation::ProvideMissingTransactions,
+    ProvideMissingTransactionsSuccess => job_negotiation::ProvideMissingTransactionsSuccess,
+    CommitMiningJob => job_negotiation::CommitMiningJob,
+    CommitMiningJobSuccess => job_negotiation::CommitMiningJobSuccess,
+    CommitMiningJobError => job_negotiation::CommitMiningJobError,
+    CoinbaseOutputDataSize => template_distribution::CoinbaseOutputDataSize,
+    NewTemplate => template_distribution::NewTemplate,
+    TemplateDistributionSetNewPrevHash => template_distribution::SetNewPrevHash,
+    RequestTransactionData => template_distribution::RequestTransactionData,
+    RequestTransactionDataSuccess => template_distribution::RequestTransactionDataSuccess,
+    RequestTransactionDataError => template_distribution::RequestTransactionDataError,
+    SubmitSolution => template_distribution::SubmitSolution
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::serialize;
+    use crate::common::SetupConnectionErrorCode;
+
+    #[test]
+    fn decode_encode_identity() {
+        let messages: Vec<AnyMessage> = vec![
+            common::SetupConnection::new_mining(
+                2,
+                2,
+                mining::SetupConnectionFlags::all(),
+                "0.0.0.0",
+                8545,
+                "Bitmain",
+                "S9i 13.5",
+                "braiins-os-2018-09-22-1-hash",
+                "some-device-uuid",
+            )
+            .unwrap()
+            .into(),
+            common::SetupConnectionError::new(
+                0x01,
+                SetupConnectionErrorCode::UnsupportedFeatureFlags,
+            )
+            .unwrap()
+            .into(),
+            mining::CloseChannel::new(1, "shutdown").unwrap().into(),
+            template_distribution::CoinbaseOutputDataSize::new(100)
+                .unwrap()
+                .into(),
+        ];
+
+        for message in messages {
+            let encoded = message.encode().unwrap();
+            assert_eq!(encoded.message_type, message.message_type());
+            assert_eq!(AnyMessage::decode(&encoded).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn decode_set_new_prev_hash() {
+        // Both sub protocols define a SetNewPrevHash message with a different
+        // MessageType.
+        let prev_hash =
+            template_distribution::SetNewPrevHash::new(1, [0u8; 32], 0, 0, [0u8; 32]).unwrap();
+        let message = frame(&prev_hash).unwrap();
+
+        assert_eq!(
+            AnyMessage::decode(&message).unwrap(),
+            AnyMessage::TemplateDistributionSetNewPrevHash(prev_hash)
+        );
+    }
+
+    #[test]
+    fn decode_extension() {
+        let message = Message::new(
+            MessageType::new_extension(0x0001, 0x00, false).unwrap(),
+            vec![0x01, 0x02],
+        );
+
+        let decoded = AnyMessage::decode(&message).unwrap();
+        assert_eq!(decoded, AnyMessage::Extension(message.clone()));
+        assert_eq!(decoded.encode().unwrap(), message);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_identity() {
+        let message: AnyMessage = common::SetupConnectionError::new(
+            0x01,
+            SetupConnectionErrorCode::UnsupportedFeatureFlags,
+        )
+        .unwrap()
+        .into();
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            json,
+            r#"{"SetupConnectionError":{"flags":1,"error_code":"unsupported-feature-flags"}}"#
+        );
+        assert_eq!(serde_json::from_str::<AnyMessage>(&json).unwrap(), message);
+
+        // Deserialization enforces the same requirements as the constructor.
+        assert!(serde_json::from_str::<AnyMessage>(
+            r#"{"SetupConnectionError":{"flags":0,"error_code":"unsupported-feature-flags"}}"#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<AnyMessage>(
+            r#"{"SetupConnectionError":{"flags":1,"error_code":"unknown-error"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn decode_err() {
+        // The payload does not match the MessageType.
+        let message = Message::new(MessageType::CloseChannel, vec![0x01]);
+        assert!(matches!(
+            AnyMessage::decode(&message),
+            Err(Error::FieldError("channel_id", 0, _))
+        ));
+
+        // The payload has bytes following the message.
+        let mut payload = serialize(&mining::CloseChannel::new(1, "").unwrap()).unwrap();
+        payload.push(0x00);
+        let message = Message::new(MessageType::CloseChannel, payload);
+        assert!(matches!(
+            AnyMessage::decode(&message),
+            Err(Error::TrailingBytes(5, 1))
+        ));
+
+        // Test messages are not implemented.
+        let message = Message::new(MessageType::TestMessage1, vec![]);
+        assert!(matches!(
+            AnyMessage::decode(&message),
+            Err(Error::UnknownMessageType())
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::codec::{deserialize, Message};
+use crate::error::Result;
+use crate::prelude::*;
+
+/// The size of a network frame header: extension type (U16), message type
+/// (U8) and message length (U24).
+pub const FRAME_HEADER_SIZE: usize = 6;
+
+/// The offset of the message length (U24) in a network frame header.
+const MESSAGE_LENGTH_OFFSET: usize = 3;
+
+/// Returns the total size of the network frame at the start of the bytes, if
+/// the bytes contain the complete frame header.
+pub(crate) fn frame_size(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < FRAME_HEADER_SIZE {
+        return None;
+    }
+
+    let mut length: [u8; 4] = [0; 4];
+    length[..3].clone_from_slice(&bytes[MESSAGE_LENGTH_OFFSET..FRAME_HEADER_SIZE]);
+
+    Some(FRAME_HEADER_SIZE + u32::from_le_bytes(length) as usize)
+}
+
+/// The result of attempting to decode the next network frame in a
+/// [FrameDecoder](struct.FrameDecoder.html).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeStatus {
+    /// A complete network frame was decoded into a Message.
+    Complete(Message),
+
+    /// The buffered bytes do not contain a complete network frame. The value
+    /// is the number of additional bytes required before the frame header or
+    /// the frame can be decoded.
+    Incomplete(usize),
+}
+
+/// FrameDecoder is a stateful decoder of network frames from a stream of
+/// bytes. Byte chunks of any size, for example from reads on a TCP stream,
+/// are pushed into the decoder and complete Messages are decoded as soon as
+/// all the bytes of their frame have been received.
+///
+/// # Examples
+///
+/// ```rust
+/// use stratumv2::codec::{DecodeStatus, FrameDecoder, Message};
+/// use stratumv2::types::MessageType;
+///
+/// let mut decoder = FrameDecoder::new();
+///
+/// decoder.push(&[0x00, 0x00, 0x10]);
+/// assert_eq!(decoder.decode().unwrap(), DecodeStatus::Incomplete(3));
+///
+/// decoder.push(&[0x01, 0x00, 0x00]);
+/// assert_eq!(decoder.decode().unwrap(), DecodeStatus::Incomplete(1));
+///
+/// decoder.push(&[0x05]);
+/// assert_eq!(
+///     decoder.decode().unwrap(),
+///     DecodeStatus::Complete(Message::new(MessageType::OpenStandardMiningChannel, vec![0x05]))
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        FrameDecoder { buffer: Vec::new() }
+    }
+
+    /// Appends a chunk of received bytes to the decoder.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the number of buffered bytes that have not been decoded.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Attempts to decode the next network frame from the buffered bytes.
+    /// Multiple frames received in a single chunk are decoded by calling this
+    /// method until it returns Incomplete.
+    ///
+    /// The bytes of a frame are consumed even if the frame fails to
+    /// deserialize, for example because of an unknown message type. This
+    /// allows decoding to continue with the following frame.
+    pub fn decode(&mut self) -> Result<DecodeStatus> {
+        let frame_size = match frame_size(&self.buffer) {
+            Some(frame_size) => frame_size,
+            None => {
+                return Ok(DecodeStatus::Incomplete(
+                    FRAME_HEADER_SIZE - self.buffer.len(),
+                ))
+            }
+        };
+
+        if self.buffer.len() < frame_size {
+            return Ok(DecodeStatus::Incomplete(frame_size - self.buffer.len()));
+        }
+
+        let message = deserialize::<Message>(&self.buffer[..frame_size]);
+        self.buffer.drain(..frame_size);
+
+        Ok(DecodeStatus::Complete(message?))
+    }
+
+    /// Appends a chunk of received bytes and decodes all the complete frames
+    /// in the buffered bytes, in order. Since the bytes of a frame that fails
+    /// to deserialize are consumed, its error is returned in place of the
+    /// Message and decoding continues with the following frame.
+    pub fn decode_all(&mut self, bytes: &[u8]) -> Vec<Result<Message>> {
+        self.push(bytes);
+
+        let mut messages = Vec::new();
+        loop {
+            match self.decode() {
+                Ok(DecodeStatus::Complete(message)) => messages.push(Ok(message)),
+                Ok(DecodeStatus::Incomplete(_)) => return messages,
+                Err(err) => messages.push(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::serialize;
+    use crate::error::Error;
+    use crate::types::MessageType;
+
+    fn make_message(payload: &[u8]) -> Message {
+        Message::new(MessageType::OpenStandardMiningChannel, payload)
+    }
+
+    #[test]
+    fn decode_byte_by_byte() {
+        let message = make_message(&[0x01, 0x02, 0x03]);
+        let serialized = serialize(&message).unwrap();
+
+        let mut decoder = FrameDecoder::new();
+        for (i, byte) in serialized.iter().enumerate() {
+            if i < FRAME_HEADER_SIZE {
+                assert_eq!(
+                    decoder.decode().unwrap(),
+                    DecodeStatus::Incomplete(FRAME_HEADER_SIZE - i)
+                );
+            } else {
+                assert_eq!(
+                    decoder.decode().unwrap(),
+                    DecodeStatus::Incomplete(serialized.len() - i)
+                );
+            }
+
+            decoder.push(&[*byte]);
+        }
+
+        assert_eq!(decoder.decode().unwrap(), DecodeStatus::Complete(message));
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+
+    #[test]
+    fn decode_multiple_frames() {
+        let first = make_message(&[0x01]);
+        let second = Message::new(MessageType::TestMessage2, vec![0x02, 0x03]);
+        let third = make_message(&[]);
+
+        let mut serialized = serialize(&first).unwrap();
+        serialized.extend(serialize(&second).unwrap());
+        serialized.extend(serialize(&third).unwrap());
+
+        // Split the third frame across two chunks.
+        let mut decoder = FrameDecoder::new();
+        let messages = decoder
+            .decode_all(&serialized[..serialized.len() - 2])
+            .into_iter()
+            .collect::<Result<Vec<Message>>>()
+            .unwrap();
+        assert_eq!(messages, vec![first, second]);
+        assert_eq!(decoder.decode().unwrap(), DecodeStatus::Incomplete(2));
+
+        let messages = decoder
+            .decode_all(&serialized[serialized.len() - 2..])
+            .into_iter()
+            .collect::<Result<Vec<Message>>>()
+            .unwrap();
+        assert_eq!(messages, vec![third]);
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+
+    #[test]
+    fn decode_error_consumes_frame() {
+        let mut decoder = FrameDecoder::new();
+
+        // Unknown message type 0xfd followed by a valid frame.
+        decoder.push(&[0x00, 0x00, 0xfd, 0x01, 0x00, 0x00, 0x05]);
+        decoder.push(&serialize(&make_message(&[0x06])).unwrap());
+
+        assert!(matches!(
+            decoder.decode(),
+            Err(Error::UnknownMessageType { .. })
+        ));
+        assert_eq!(
+            decoder.decode().unwrap(),
+            DecodeStatus::Complete(make_message(&[0x06]))
+        );
+    }
+
+    #[test]
+    fn decode_all_error_keeps_frames() {
+        let first = make_message(&[0x01]);
+        let third = make_message(&[0x03]);
+
+        // A valid frame, a frame with the unknown message type 0xfd and a
+        // valid frame in a single chunk.
+        let mut serialized = serialize(&first).unwrap();
+        serialized.extend(&[0x00, 0x00, 0xfd, 0x01, 0x00, 0x00, 0x02]);
+        serialized.extend(serialize(&third).unwrap());
+
+        let mut decoder = FrameDecoder::new();
+        let mut messages = decoder.decode_all(&serialized).into_iter();
+
+        assert_eq!(messages.next().unwrap().unwrap(), first);
+        assert!(matches!(
+            messages.next().unwrap(),
+            Err(Error::UnknownMessageType { .. })
+        ));
+        assert_eq!(messages.next().unwrap().unwrap(), third);
+        assert!(messages.next().is_none());
+        assert_eq!(decoder.buffered_len(), 0);
+    }
+}
@@ -1,15 +1,77 @@
-use crate::{codec::ByteParser, error::Result};
+use crate::{
+    codec::ByteParser,
+    error::{Error, Result},
+    prelude::*,
+};
 
 /// Trait for deserializing bytes to most Stratum V2 messages.
 pub trait Deserializable {
     fn deserialize(parser: &mut ByteParser) -> Result<Self>
     where
-        Self: std::marker::Sized;
+        Self: core::marker::Sized;
 }
 
 /// Helper utility function to deserialize a byte-stream into a type that
 /// implements the Serializable trait and returns the deserialized result.
+/// Bytes following the deserialized type are ignored.
 pub fn deserialize<T: Deserializable>(bytes: &[u8]) -> Result<T> {
     let mut parser = ByteParser::new(bytes, 0);
     T::deserialize(&mut parser)
 }
+
+/// Deserializes a byte-stream into a type that implements the Deserializable
+/// trait, returning an error if any bytes follow the deserialized type.
+pub fn deserialize_strict<T: Deserializable>(bytes: &[u8]) -> Result<T> {
+    let mut parser = ByteParser::new(bytes, 0);
+    let deserialized = T::deserialize(&mut parser)?;
+    parser.expect_end()?;
+
+    Ok(deserialized)
+}
+
+/// Deserializes a field of a message. Errors are wrapped in a FieldError with
+/// the name of the field and the offset where the field starts, so a failure
+/// can be located in the received bytes.
+pub fn deserialize_field<T: Deserializable>(
+    parser: &mut ByteParser,
+    field: &'static str,
+) -> Result<T> {
+    let offset = parser.position();
+    T::deserialize(parser).map_err(|e| Error::FieldError(field, offset, Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::STR0_255;
+
+    #[test]
+    fn deserialize_trailing_bytes() {
+        let bytes = [0x01, 0x00, 0xff];
+
+        assert_eq!(deserialize::<u16>(&bytes).unwrap(), 1);
+        assert!(matches!(
+            deserialize_strict::<u16>(&bytes),
+            Err(Error::TrailingBytes(2, 1))
+        ));
+        assert_eq!(deserialize_strict::<u16>(&bytes[..2]).unwrap(), 1);
+    }
+
+    #[test]
+    fn deserialize_field_err() {
+        // The string promises 3 bytes but only 1 follows.
+        let bytes = [0x00, 0x03, 0x61];
+        let mut parser = ByteParser::new(&bytes, 0);
+        assert_eq!(deserialize_field::<u8>(&mut parser, "a").unwrap(), 0);
+
+        let err = deserialize_field::<STR0_255>(&mut parser, "b").unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::FieldError("b", 1, e) if matches!(**e, Error::OutOfBounds(2, 3, 1))
+        ));
+        assert_eq!(
+            err.to_string(),
+            "failed to deserialize `b` at offset `1`: expected `3` bytes at offset `2` but only `1` remain"
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use crate::codec::{serialize, ByteParser, Deserializable, Serializable};
+use crate::codec::decoder::FRAME_HEADER_SIZE;
+use crate::codec::{io, serialize, ByteParser, Deserializable, Serializable};
 use crate::error::{Error, Result};
+use crate::prelude::*;
 use crate::types::{MessageType, U24};
-use std::io;
 
 /// The CHANNEL_BIT_MASK is used to mask out the MSB to identify if a message
 /// type has a channel id in a message frame.
@@ -16,8 +17,16 @@ const EXTENSION_TYPE_MASK: u16 = 0x7FFF;
 /// used to store messages on an outgoing buffer before being processed
 /// and sent over the wire.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Message {
     pub message_type: MessageType,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::types::hex::serialize",
+            deserialize_with = "crate::types::hex::deserialize"
+        )
+    )]
     pub payload: Vec<u8>,
 }
 
@@ -32,6 +41,8 @@ impl Message {
 
 impl Serializable for Message {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        self.message_type.verify()?;
+
         let message_type: u8 = self.message_type.msg_type();
         let mut extension_type: u16 = self.message_type.ext_type();
 
@@ -42,34 +53,127 @@ impl Serializable for Message {
 
         let message_length = U24::new(self.payload.len() as u32)?;
 
-        Ok([
+        let header_length: usize = [
             extension_type.serialize(writer)?,
             message_type.serialize(writer)?,
             message_length.serialize(writer)?,
-            writer.write(self.payload.as_slice())?,
         ]
         .iter()
-        .sum())
+        .sum();
+        writer.write_all(self.payload.as_slice())?;
+
+        Ok(header_length + self.payload.len())
+    }
+
+    fn encoded_len(&self) -> usize {
+        FRAME_HEADER_SIZE + self.payload.len()
     }
 }
 
 impl Deserializable for Message {
     fn deserialize(parser: &mut ByteParser) -> Result<Message> {
+        Ok(MessageRef::deserialize(parser)?.into())
+    }
+}
+
+/// A borrowed network frame. The payload is a slice of the received bytes,
+/// allowing a frame to be inspected and routed without copying the payload.
+/// MessageRef can be converted into an owned [Message](struct.Message.html)
+/// when the payload needs to outlive the received bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MessageRef<'a> {
+    pub message_type: MessageType,
+    pub payload: &'a [u8],
+}
+
+impl<'a> MessageRef<'a> {
+    pub fn new(message_type: MessageType, payload: &'a [u8]) -> MessageRef<'a> {
+        MessageRef {
+            message_type,
+            payload,
+        }
+    }
+
+    /// Deserializes a network frame from a ByteParser. The payload borrows
+    /// from the bytes of the ByteParser.
+    pub fn deserialize(parser: &mut ByteParser<'a>) -> Result<MessageRef<'a>> {
         let mut extension_type = u16::deserialize(parser)?;
         let channel_bit: bool = (extension_type & CHANNEL_BIT_MASK) != 0;
         extension_type &= EXTENSION_TYPE_MASK;
 
-        let message_type = MessageType::new(extension_type, u8::deserialize(parser)?)?;
+        let message_type = u8::deserialize(parser)?;
 
-        if message_type.channel_bit() != channel_bit {
-            return Err(Error::UnexpectedChannelBit(channel_bit));
-        }
+        // Messages with a non-zero extension_type belong to a protocol
+        // extension and are passed through with the received channel bit.
+        let message_type = if extension_type == 0 {
+            let message_type = MessageType::new(extension_type, message_type)?;
+            if message_type.channel_bit() != channel_bit {
+                return Err(Error::UnexpectedChannelBit(channel_bit));
+            }
+            message_type
+        } else {
+            MessageType::new_extension(extension_type, message_type, channel_bit)?
+        };
 
         let message_length = U24::deserialize(parser)?;
         let payload = parser.next_by(message_length.0 as usize)?;
 
-        // TODO(chpatton013): Make this operation zero-copy by taking the contents of the parser.
-        Ok(Message::new(message_type, payload.to_vec()))
+        Ok(MessageRef::new(message_type, payload))
+    }
+
+    /// Returns the channel_id of a message with the channel bit set. The
+    /// channel_id is always the first field of these messages, so it can be
+    /// read without deserializing the payload. None is returned if the message
+    /// has no channel_id or the payload is too short.
+    pub fn channel_id(&self) -> Option<u32> {
+        if !self.message_type.channel_bit() {
+            return None;
+        }
+
+        let mut parser = ByteParser::new(self.payload, 0);
+        u32::deserialize(&mut parser).ok()
+    }
+
+    /// Deserializes the payload into a type that implements the Frameable
+    /// trait. An error is returned if the payload has bytes following the
+    /// message.
+    pub fn unframe<T: Frameable>(&self) -> Result<T> {
+        let mut parser = ByteParser::new(self.payload, 0);
+        let message = self.unframe_from(&mut parser)?;
+        parser.expect_end()?;
+
+        Ok(message)
+    }
+
+    /// Deserializes the payload into a type that implements the Frameable
+    /// trait, ignoring any bytes following the message. This allows messages
+    /// from peers that append fields to a message to be read.
+    pub fn unframe_lenient<T: Frameable>(&self) -> Result<T> {
+        self.unframe_from(&mut ByteParser::new(self.payload, 0))
+    }
+
+    fn unframe_from<T: Frameable>(&self, parser: &mut ByteParser<'a>) -> Result<T> {
+        let expected_message_type = T::message_type();
+        if expected_message_type != self.message_type {
+            return Err(Error::UnexpectedMessageType(
+                expected_message_type.ext_type(),
+                expected_message_type.msg_type(),
+            ));
+        }
+
+        T::deserialize(parser)
+    }
+}
+
+impl<'a> From<&'a Message> for MessageRef<'a> {
+    fn from(message: &'a Message) -> Self {
+        MessageRef::new(message.message_type, message.payload.as_slice())
+    }
+}
+
+impl<'a> From<MessageRef<'a>> for Message {
+    fn from(message: MessageRef<'a>) -> Self {
+        Message::new(message.message_type, message.payload)
     }
 }
 
@@ -85,19 +189,16 @@ pub fn frame<T: Frameable>(payload: &T) -> Result<Message> {
 }
 
 /// Utility function to convert a network frame message into a type that implements
-/// the Frameable trait.
+/// the Frameable trait. An error is returned if the payload has bytes following
+/// the message.
 pub fn unframe<T: Frameable>(message: &Message) -> Result<T> {
-    let expected_message_type = T::message_type();
-    if expected_message_type != message.message_type {
-        return Err(Error::UnexpectedMessageType(
-            expected_message_type.ext_type(),
-            expected_message_type.msg_type(),
-        ));
-    }
-
-    let mut parser = ByteParser::new(message.payload.as_slice(), 0);
+    MessageRef::from(message).unframe()
+}
 
-    T::deserialize(&mut parser)
+/// Utility function to convert a network frame message into a type that implements
+/// the Frameable trait, ignoring any bytes following the message.
+pub fn unframe_lenient<T: Frameable>(message: &Message) -> Result<T> {
+    MessageRef::from(message).unframe_lenient()
 }
 
 #[cfg(test)]
@@ -105,6 +206,7 @@ mod tests {
     use super::*;
     use crate::codec::{deserialize, serialize};
     use crate::impl_message;
+    use crate::types::{B0_255, U256};
 
     impl_message!(TestMessage1, x u8);
 
@@ -161,4 +263,219 @@ mod tests {
             deserialized
         );
     }
+
+    #[test]
+    fn message_ref_deserialize() {
+        let serialized = vec![
+            0x00, 0x80, // extension type & channel bit (MSB=1)
+            0xff, // message type
+            0x05, 0x00, 0x00, // message length
+            0x01, 0x02, 0x03, 0x04, 0x05, // message payload
+        ];
+
+        let mut parser = ByteParser::new(&serialized, 0);
+        let message = MessageRef::deserialize(&mut parser).unwrap();
+
+        assert_eq!(message.message_type, MessageType::TestMessage2);
+        assert!(std::ptr::eq(message.payload, &serialized[6..]));
+        assert_eq!(message.channel_id(), Some(0x04030201));
+        assert_eq!(
+            Message::from(message),
+            deserialize::<Message>(&serialized).unwrap()
+        );
+    }
+
+    #[test]
+    fn message_ref_channel_id() {
+        // The message type has no channel_id.
+        let message = MessageRef::new(MessageType::TestMessage1, &[0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(message.channel_id(), None);
+
+        // The payload is too short to contain a channel_id.
+        let message = MessageRef::new(MessageType::TestMessage2, &[0x01, 0x02]);
+        assert_eq!(message.channel_id(), None);
+    }
+
+    #[test]
+    fn message_ref_unframe() {
+        let serialized = [0x05];
+        let message = MessageRef::new(MessageType::TestMessage1, &serialized);
+
+        assert_eq!(
+            message.unframe::<TestMessage1>().unwrap(),
+            TestMessage1::new(5u8).unwrap()
+        );
+        assert!(matches!(
+            message.unframe::<TestMessage2>(),
+            Err(Error::UnexpectedMessageType { .. })
+        ));
+    }
+
+    #[test]
+    fn unframe_trailing_bytes() {
+        let framed = Message::new(MessageType::TestMessage1, vec![0x05, 0x06, 0x07]);
+
+        assert!(matches!(
+            unframe::<TestMessage1>(&framed),
+            Err(Error::TrailingBytes(1, 2))
+        ));
+        assert_eq!(
+            unframe_lenient::<TestMessage1>(&framed).unwrap(),
+            TestMessage1::new(5u8).unwrap()
+        );
+    }
+
+    #[test]
+    fn unframe_field_err() {
+        // The payload ends in the middle of the B0_255 data.
+        let framed = Message::new(
+            MessageType::TestMessage2,
+            vec![0x01, 0x00, 0x00, 0x00, 0x02],
+        );
+
+        let err = unframe::<DerivedMessage>(&framed).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::FieldError("data", 4, e) if matches!(**e, Error::OutOfBounds(5, 2, 0))
+        ));
+    }
+
+    // Messages defined with the derive macros instead of impl_message.
+    #[derive(Debug, PartialEq, Serializable, Deserializable, Frameable)]
+    #[sv2(message_type = TestMessage2, channel_bit = true)]
+    struct DerivedMessage {
+        channel_id: u32,
+        data: B0_255,
+    }
+
+    #[derive(Debug, PartialEq, Serializable, Deserializable, Frameable)]
+    #[sv2(message_type = TestMessage1)]
+    struct DerivedTupleMessage(u8, U256);
+
+    #[test]
+    fn derived_message_frame_identity() {
+        let unframed = DerivedMessage {
+            channel_id: 1,
+            data: B0_255::new([0x0a, 0x0b]).unwrap(),
+        };
+        let framed = Message::new(
+            MessageType::TestMessage2,
+            vec![
+                0x01, 0x00, 0x00, 0x00, // channel_id
+                0x02, 0x0a, 0x0b, // data
+            ],
+        );
+
+        assert_eq!(frame(&unframed).unwrap(), framed);
+        assert_eq!(unframe::<DerivedMessage>(&framed).unwrap(), unframed);
+        assert_eq!(MessageRef::from(&framed).channel_id(), Some(1));
+    }
+
+    #[test]
+    fn derived_tuple_message_serde_identity() {
+        let deserialized = DerivedTupleMessage(5, U256([0xff; 32]));
+        let mut serialized = vec![0x05];
+        serialized.extend(&[0xff; 32]);
+
+        assert_eq!(serialize(&deserialized).unwrap(), serialized);
+        assert_eq!(deserialized.encoded_len(), serialized.len());
+        assert_eq!(
+            deserialize::<DerivedTupleMessage>(&serialized).unwrap(),
+            deserialized
+        );
+        assert_eq!(
+            DerivedTupleMessage::message_type(),
+            MessageType::TestMessage1
+        );
+    }
+
+    #[test]
+    fn extension_frame_passthrough() {
+        let serialized = vec![
+            0x02, 0x80, // extension type & channel bit (MSB=1)
+            0x10, // message type
+            0x05, 0x00, 0x00, // message length
+            0x01, 0x00, 0x00, 0x00, 0xff, // message payload
+        ];
+
+        let message = deserialize::<Message>(&serialized).unwrap();
+        assert_eq!(
+            message.message_type,
+            MessageType::new_extension(0x0002, 0x10, true).unwrap()
+        );
+        assert_eq!(MessageRef::from(&message).channel_id(), Some(1));
+
+        // The unknown extension message is serialized without modification.
+        assert_eq!(serialize(&message).unwrap(), serialized);
+    }
+
+    #[test]
+    fn serialize_short_write_err() {
+        let message = Message::new(MessageType::TestMessage1, vec![0x01, 0x02]);
+
+        // The buffer has room for the frame header but not the payload.
+        let mut buffer = [0u8; 7];
+        assert!(message.serialize(&mut &mut buffer[..]).is_err());
+    }
+
+    #[test]
+    fn extension_frame_invalid_ext_type() {
+        // The extension_type would overlap the channel bit or the messages of
+        // the specification.
+        for ext_type in &[0x0000, 0x8000] {
+            let message_type = MessageType::Extension {
+                ext_type: *ext_type,
+                msg_type: 0x10,
+                channel_bit: false,
+            };
+
+            assert!(matches!(
+                serialize(&Message::new(message_type, vec![0x01])),
+                Err(Error::RequirementError { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn core_frame_unexpected_channel_bit() {
+        let serialized = vec![
+            0x00, 0x80, // extension type & channel bit (MSB=1)
+            0xfe, // message type
+            0x00, 0x00, 0x00, // message length
+        ];
+
+        assert!(matches!(
+            deserialize::<Message>(&serialized),
+            Err(Error::UnexpectedChannelBit(true))
+        ));
+    }
+
+    #[derive(Debug, PartialEq, Serializable, Deserializable, Frameable)]
+    #[sv2(extension_type = 0x0002, message_type = 0x10, channel_bit = true)]
+    struct ExtensionMessage {
+        channel_id: u32,
+        value: u8,
+    }
+
+    #[test]
+    fn derived_extension_message_frame_identity() {
+        let unframed = ExtensionMessage {
+            channel_id: 1,
+            value: 0xff,
+        };
+        let framed = Message::new(
+            MessageType::new_extension(0x0002, 0x10, true).unwrap(),
+            vec![
+                0x01, 0x00, 0x00, 0x00, // channel_id
+                0xff, // value
+            ],
+        );
+
+        assert_eq!(frame(&unframed).unwrap(), framed);
+        assert_eq!(unframe::<ExtensionMessage>(&framed).unwrap(), unframed);
+        assert!(matches!(
+            unframe::<DerivedMessage>(&framed),
+            Err(Error::UnexpectedMessageType(0x0000, 0xff))
+        ));
+    }
 }
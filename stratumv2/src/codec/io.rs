@@ -0,0 +1,93 @@
+//! The writer used by the [Serializable](../trait.Serializable.html) trait.
+//!
+//! With the `std` feature, [Write](trait.Write.html) is `std::io::Write`, so
+//! messages can be serialized into any std writer. Without std, a minimal
+//! Write trait is implemented for `Vec<u8>` and `&mut [u8]`.
+
+#[cfg(feature = "std")]
+pub use std::io::Write;
+
+#[cfg(not(feature = "std"))]
+use crate::{
+    error::{Error, Result},
+    prelude::*,
+};
+
+/// A minimal replacement of std::io::Write used to serialize messages without
+/// std.
+#[cfg(not(feature = "std"))]
+pub trait Write {
+    /// Writes the bytes into the writer and returns the number of bytes
+    /// written, which is less than the length of the bytes if the writer is
+    /// full.
+    fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+    /// Writes all the bytes into the writer. An error is returned if the
+    /// writer is full.
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let length = self.write(buf)?;
+        if length < buf.len() {
+            return Err(Error::BufferTooSmall(length, buf.len()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Write for Vec<u8> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+/// Writing into a slice overwrites its bytes and advances the slice past the
+/// written bytes.
+#[cfg(not(feature = "std"))]
+impl Write for &mut [u8] {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let length = core::cmp::min(buf.len(), self.len());
+        let (head, tail) = core::mem::take(self).split_at_mut(length);
+        head.copy_from_slice(&buf[..length]);
+        *self = tail;
+
+        Ok(length)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<W: Write + ?Sized> Write for &mut W {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_vec() {
+        let mut buffer = vec![0x01];
+        assert_eq!(buffer.write(&[0x02, 0x03]).unwrap(), 2);
+        assert_eq!(buffer, vec![0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn write_slice() {
+        let mut buffer = [0u8; 3];
+        let mut slice = &mut buffer[..];
+
+        assert_eq!(slice.write(&[0x01, 0x02]).unwrap(), 2);
+        assert_eq!(slice.write(&[0x03, 0x04]).unwrap(), 1);
+        assert_eq!(slice.write(&[0x05]).unwrap(), 0);
+        assert_eq!(buffer, [0x01, 0x02, 0x03]);
+
+        let mut buffer = [0u8; 1];
+        assert!(matches!(
+            (&mut buffer[..]).write_all(&[0x01, 0x02]),
+            Err(Error::BufferTooSmall(1, 2))
+        ));
+    }
+}
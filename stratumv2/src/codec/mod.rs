@@ -1,10 +1,22 @@
+mod any_message;
+mod decoder;
 mod deserialize;
 mod frame;
+pub mod io;
 mod parser;
 mod serialize;
+#[cfg(feature = "tokio")]
+mod tokio_codec;
 
-pub use deserialize::{deserialize, Deserializable};
+pub use any_message::AnyMessage;
+#[cfg(feature = "std")]
+pub(crate) use decoder::frame_size;
+pub use decoder::{DecodeStatus, FrameDecoder, FRAME_HEADER_SIZE};
+pub use deserialize::{deserialize, deserialize_field, deserialize_strict, Deserializable};
 pub(crate) use frame::CHANNEL_BIT_MASK;
-pub use frame::{frame, unframe, Frameable, Message};
+pub use frame::{frame, unframe, unframe_lenient, Frameable, Message, MessageRef};
 pub use parser::ByteParser;
-pub use serialize::{serialize, Serializable};
+pub use serialize::{serialize, serialize_into, Serializable};
+pub use stratumv2_derive::{Deserializable, Frameable, Serializable};
+#[cfg(feature = "tokio")]
+pub use tokio_codec::Sv2Codec;
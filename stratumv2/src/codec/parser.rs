@@ -18,10 +18,66 @@ impl<'a> ByteParser<'a> {
 
         let b = self.bytes.get(self.start..offset);
         if b.is_none() {
-            return Err(Error::ParseError("out of bounds error".into()));
+            return Err(Error::OutOfBounds(self.start, step, self.remaining()));
         }
 
         self.start = offset;
         Ok(b.unwrap())
     }
+
+    /// Returns the offset of the next byte to be parsed.
+    pub fn position(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the number of bytes that have not been parsed.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.start)
+    }
+
+    /// Returns an error if any bytes have not been parsed, used to reject
+    /// messages with trailing bytes.
+    pub fn expect_end(&self) -> Result<()> {
+        if self.remaining() > 0 {
+            return Err(Error::TrailingBytes(self.start, self.remaining()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_by() {
+        let bytes = [0x01, 0x02, 0x03];
+        let mut parser = ByteParser::new(&bytes, 0);
+
+        assert_eq!(parser.next_by(2).unwrap(), &[0x01, 0x02]);
+        assert_eq!(parser.position(), 2);
+        assert_eq!(parser.remaining(), 1);
+        assert!(matches!(
+            parser.expect_end(),
+            Err(Error::TrailingBytes(2, 1))
+        ));
+
+        // A failed read does not advance the parser.
+        assert!(matches!(
+            parser.next_by(2),
+            Err(Error::OutOfBounds(2, 2, 1))
+        ));
+        assert_eq!(parser.position(), 2);
+
+        assert_eq!(parser.next_by(1).unwrap(), &[0x03]);
+        assert_eq!(parser.remaining(), 0);
+        assert!(parser.expect_end().is_ok());
+    }
+
+    #[test]
+    fn start_past_end() {
+        let parser = ByteParser::new(&[0x01], 2);
+        assert_eq!(parser.remaining(), 0);
+    }
 }
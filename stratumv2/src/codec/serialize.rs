@@ -1,17 +1,135 @@
-use crate::error::Result;
-use std::io;
+use crate::codec::io;
+use crate::error::{Error, Result};
+use crate::prelude::*;
 
 /// Trait for encoding and serializing messages and objects according to the
 /// Stratum V2 protocol.
 pub trait Serializable {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize>;
+
+    /// Returns the exact number of bytes written when serializing. This allows
+    /// buffers to be sized before serialization.
+    ///
+    /// By default, the bytes are counted by serializing into a writer that
+    /// discards them. Types that can compute their length without serializing
+    /// should override it.
+    fn encoded_len(&self) -> usize {
+        let mut counter = ByteCounter(0);
+
+        // A value that fails to serialize fails again when it is serialized
+        // into the sized buffer, so the error can be ignored.
+        let _ = self.serialize(&mut counter);
+        counter.0
+    }
+}
+
+/// A writer that discards the bytes written into it and counts them.
+struct ByteCounter(usize);
+
+#[cfg(feature = "std")]
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
 }
 
 /// Helper utility function to serialize a type that implements the Serializable
 /// trait and returns the serialized result.
 pub fn serialize<T: Serializable>(val: &T) -> Result<Vec<u8>> {
-    let mut buffer = vec![];
+    let mut buffer = Vec::with_capacity(val.encoded_len());
     val.serialize(&mut buffer)?;
 
     Ok(buffer)
 }
+
+/// Helper utility function to serialize a type that implements the Serializable
+/// trait into the start of an existing buffer, without allocating. Returns the
+/// number of bytes written or an error if the buffer is too small. The value
+/// is serialized into the first encoded_len bytes of the buffer, so an
+/// encoded_len that under-reports the length is also an error.
+pub fn serialize_into<T: Serializable>(val: &T, buffer: &mut [u8]) -> Result<usize> {
+    let length = val.encoded_len();
+    if buffer.len() < length {
+        return Err(Error::BufferTooSmall(buffer.len(), length));
+    }
+
+    val.serialize(&mut &mut buffer[..length])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::Message;
+    use crate::types::{MessageType, STR0_255};
+
+    /// A type relying on the default encoded_len.
+    struct Pair(u32, STR0_255);
+
+    impl Serializable for Pair {
+        fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+            Ok(self.0.serialize(writer)? + self.1.serialize(writer)?)
+        }
+    }
+
+    /// A type whose encoded_len under-reports the serialized length.
+    struct Underreported(u32);
+
+    impl Serializable for Underreported {
+        fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+            self.0.serialize(writer)
+        }
+
+        fn encoded_len(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn serialize_into_underreported_len() {
+        let mut buffer = [0; 8];
+        assert!(serialize_into(&Underreported(0x01020304), &mut buffer).is_err());
+    }
+
+    #[test]
+    fn default_encoded_len() {
+        let pair = Pair(1, STR0_255::new("test").unwrap());
+
+        assert_eq!(pair.encoded_len(), 9);
+        assert_eq!(pair.encoded_len(), serialize(&pair).unwrap().len());
+    }
+
+    #[test]
+    fn serialize_into_buffer() {
+        let message = Message::new(MessageType::TestMessage1, vec![0x01, 0x02]);
+        let mut buffer = [0xff; 10];
+
+        assert_eq!(serialize_into(&message, &mut buffer).unwrap(), 8);
+        assert_eq!(buffer[..8], serialize(&message).unwrap()[..]);
+
+        // The bytes after the serialized message are left untouched.
+        assert_eq!(buffer[8..], [0xff, 0xff]);
+    }
+
+    #[test]
+    fn serialize_into_small_buffer() {
+        let mut buffer = [0; 3];
+        assert!(matches!(
+            serialize_into(&0x01020304u32, &mut buffer),
+            Err(Error::BufferTooSmall(3, 4))
+        ));
+        assert_eq!(buffer, [0; 3]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::codec::decoder::{frame_size, FRAME_HEADER_SIZE};
+use crate::codec::{deserialize, serialize_into, Message, Serializable};
+use crate::error::Error;
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Sv2Codec implements the tokio_util Decoder and Encoder traits for network
+/// frames, allowing a [Message](struct.Message.html) stream and sink to be
+/// created from any AsyncRead/AsyncWrite, such as a TcpStream, using
+/// `Framed::new(stream, Sv2Codec)`.
+///
+/// This codec only handles unencrypted network frames.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytes::BytesMut;
+/// use stratumv2::codec::{Message, Sv2Codec};
+/// use stratumv2::types::MessageType;
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let message = Message::new(MessageType::OpenStandardMiningChannel, vec![0x05]);
+///
+/// let mut buffer = BytesMut::new();
+/// Sv2Codec.encode(message.clone(), &mut buffer).unwrap();
+///
+/// assert_eq!(Sv2Codec.decode(&mut buffer).unwrap(), Some(message));
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Sv2Codec;
+
+impl Decoder for Sv2Codec {
+    type Item = Message;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Message>, Error> {
+        let frame_size = match frame_size(src) {
+            Some(frame_size) => frame_size,
+            None => {
+                src.reserve(FRAME_HEADER_SIZE - src.len());
+                return Ok(None);
+            }
+        };
+
+        if src.len() < frame_size {
+            src.reserve(frame_size - src.len());
+            return Ok(None);
+        }
+
+        // The frame is consumed even if it fails to deserialize, so that the
+        // following frames can still be decoded.
+        let frame = src.split_to(frame_size);
+        Ok(Some(deserialize::<Message>(&frame)?))
+    }
+}
+
+impl Encoder<Message> for Sv2Codec {
+    type Error = Error;
+
+    fn encode(&mut self, item: Message, dst: &mut BytesMut) -> Result<(), Error> {
+        // Serialize directly into the destination buffer.
+        let start = dst.len();
+        dst.resize(start + item.encoded_len(), 0);
+
+        // Remove the reserved bytes if the message can't be serialized, so
+        // the frames already in the buffer can still be sent.
+        if let Err(err) = serialize_into(&item, &mut dst[start..]) {
+            dst.truncate(start);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::serialize;
+    use crate::types::MessageType;
+    use bytes::BufMut;
+
+    fn make_message(payload: &[u8]) -> Message {
+        Message::new(MessageType::OpenStandardMiningChannel, payload)
+    }
+
+    #[test]
+    fn decode_partial_frames() {
+        let first = make_message(&[0x01, 0x02]);
+        let second = make_message(&[0x03]);
+
+        let mut serialized = serialize(&first).unwrap();
+        serialized.extend(serialize(&second).unwrap());
+
+        let mut buffer = BytesMut::new();
+        buffer.put_slice(&serialized[..4]);
+        assert_eq!(Sv2Codec.decode(&mut buffer).unwrap(), None);
+
+        buffer.put_slice(&serialized[4..10]);
+        assert_eq!(Sv2Codec.decode(&mut buffer).unwrap(), Some(first));
+        assert_eq!(Sv2Codec.decode(&mut buffer).unwrap(), None);
+
+        buffer.put_slice(&serialized[10..]);
+        assert_eq!(Sv2Codec.decode(&mut buffer).unwrap(), Some(second));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn encode_frames() {
+        let message = make_message(&[0x01]);
+
+        let mut buffer = BytesMut::new();
+        Sv2Codec.encode(message.clone(), &mut buffer).unwrap();
+        Sv2Codec.encode(message.clone(), &mut buffer).unwrap();
+
+        let mut expected = serialize(&message).unwrap();
+        expected.extend(serialize(&message).unwrap());
+        assert_eq!(buffer.to_vec(), expected);
+    }
+
+    #[test]
+    fn encode_error_leaves_buffer() {
+        let message = make_message(&[0x01]);
+        let invalid = Message::new(
+            MessageType::Extension {
+                ext_type: 0x8000,
+                msg_type: 0x00,
+                channel_bit: false,
+            },
+            vec![0x01],
+        );
+
+        let mut buffer = BytesMut::new();
+        Sv2Codec.encode(message.clone(), &mut buffer).unwrap();
+        assert!(matches!(
+            Sv2Codec.encode(invalid, &mut buffer),
+            Err(Error::RequirementError { .. })
+        ));
+        assert_eq!(buffer.to_vec(), serialize(&message).unwrap());
+    }
+
+    #[test]
+    fn decode_error_consumes_frame() {
+        let mut buffer = BytesMut::new();
+        buffer.put_slice(&[0x00, 0x00, 0xfd, 0x01, 0x00, 0x00, 0x05]);
+        buffer.put_slice(&serialize(&make_message(&[0x06])).unwrap());
+
+        assert!(matches!(
+            Sv2Codec.decode(&mut buffer),
+            Err(Error::UnknownMessageType { .. })
+        ));
+        assert_eq!(
+            Sv2Codec.decode(&mut buffer).unwrap(),
+            Some(make_message(&[0x06]))
+        );
+    }
+}
@@ -1,10 +1,9 @@
 use crate::{
-    codec::{ByteParser, Deserializable, Frameable, Serializable},
+    codec::{io, ByteParser, Deserializable, Frameable, Serializable},
     error::Result,
     impl_message,
     types::MessageType,
 };
-use std::io;
 
 impl_message!(
     /// When a channel’s upstream or downstream endpoint changes and that channel had previously sent
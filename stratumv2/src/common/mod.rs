@@ -1,7 +1,11 @@
 mod channel_endpoint_changed;
 mod setup_connection;
+mod setup_connection_error;
 mod setup_connection_error_code;
+mod setup_connection_success;
 
 pub use channel_endpoint_changed::ChannelEndpointChanged;
 pub use setup_connection::{Protocol, SetupConnection};
+pub use setup_connection_error::SetupConnectionError;
 pub use setup_connection_error_code::SetupConnectionErrorCode;
+pub use setup_connection_success::SetupConnectionSuccess;
@@ -1,15 +1,16 @@
-use crate::codec::{ByteParser, Deserializable, Frameable, Serializable};
+use crate::codec::{deserialize_field, io, ByteParser, Deserializable, Frameable, Serializable};
 use crate::error::{Error, Result};
+use crate::job_distribution;
 use crate::job_negotiation;
 use crate::mining;
+use crate::prelude::*;
+use crate::template_distribution;
 use crate::types::MessageType;
-// use crate::template_distribution;
-// use crate::job_distribution;
-use std::convert::TryFrom;
-use std::io;
+use core::convert::TryFrom;
 
 /// Protocol is an enum representing each sub protocol of Stratum V2.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Protocol {
     /// Mining is the main and only required sub protocol in Stratum V2.
     Mining,
@@ -67,6 +68,10 @@ impl Serializable for Protocol {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         Ok(u8::from(self).serialize(writer)?)
     }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl Deserializable for Protocol {
@@ -78,11 +83,13 @@ impl Deserializable for Protocol {
 /// Contains all the variants of each subprotocols SetupConnection message.
 /// When constructing a NetworkMessage this enum should be used to correctly
 /// serialize the SetupConnection specific to the subprotocol.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SetupConnection {
     Mining(mining::SetupConnection),
     JobNegotiation(job_negotiation::SetupConnection),
-    // TemplateDistribution(template_distribution::SetupConnection),
-    // JobDistribution(job_distribution::SetupConnection),
+    TemplateDistribution(template_distribution::SetupConnection),
+    JobDistribution(job_distribution::SetupConnection),
 }
 
 impl SetupConnection {
@@ -175,6 +182,110 @@ impl SetupConnection {
             )?,
         ))
     }
+
+    /// SetupConnection message for the template distribution subprotocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::template_distribution::SetupConnectionFlags;
+    /// use stratumv2::common::SetupConnection;
+    ///
+    /// let new_connection = SetupConnection::new_template_distribution(
+    ///    2,
+    ///    2,
+    ///    SetupConnectionFlags::NONE,
+    ///    "0.0.0.0",
+    ///    8545,
+    ///    "Bitmain",
+    ///    "S9i 13.5",
+    ///    "braiins-os-2018-09-22-1-hash",
+    ///    "some-device-uuid",
+    /// );
+    /// assert!(new_connection.is_ok());
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_template_distribution<T: Into<String>>(
+        min_version: u16,
+        max_version: u16,
+        flags: template_distribution::SetupConnectionFlags,
+        endpoint_host: T,
+        endpoint_port: u16,
+        vendor: T,
+        hardware_version: T,
+        firmware: T,
+        device_id: T,
+    ) -> Result<SetupConnection> {
+        Ok(SetupConnection::TemplateDistribution(
+            template_distribution::SetupConnection::new(
+                min_version,
+                max_version,
+                flags,
+                endpoint_host,
+                endpoint_port,
+                vendor,
+                hardware_version,
+                firmware,
+                device_id,
+            )?,
+        ))
+    }
+
+    /// SetupConnection message for the job distribution subprotocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::job_distribution::SetupConnectionFlags;
+    /// use stratumv2::common::SetupConnection;
+    ///
+    /// let new_connection = SetupConnection::new_job_distribution(
+    ///    2,
+    ///    2,
+    ///    SetupConnectionFlags::NONE,
+    ///    "0.0.0.0",
+    ///    8545,
+    ///    "Bitmain",
+    ///    "S9i 13.5",
+    ///    "braiins-os-2018-09-22-1-hash",
+    ///    "some-device-uuid",
+    /// );
+    /// assert!(new_connection.is_ok());
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_job_distribution<T: Into<String>>(
+        min_version: u16,
+        max_version: u16,
+        flags: job_distribution::SetupConnectionFlags,
+        endpoint_host: T,
+        endpoint_port: u16,
+        vendor: T,
+        hardware_version: T,
+        firmware: T,
+        device_id: T,
+    ) -> Result<SetupConnection> {
+        Ok(SetupConnection::JobDistribution(
+            job_distribution::SetupConnection::new(
+                min_version,
+                max_version,
+                flags,
+                endpoint_host,
+                endpoint_port,
+                vendor,
+                hardware_version,
+                firmware,
+                device_id,
+            )?,
+        ))
+    }
+
+    /// Returns the sub protocol of the connection.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            SetupConnection::Mining(_) => Protocol::Mining,
+            SetupConnection::JobNegotiation(_) => Protocol::JobNegotiation,
+            SetupConnection::TemplateDistribution(_) => Protocol::TemplateDistribution,
+            SetupConnection::JobDistribution(_) => Protocol::JobDistribution,
+        }
+    }
 }
 
 impl Serializable for SetupConnection {
@@ -185,21 +296,37 @@ impl Serializable for SetupConnection {
             }
             SetupConnection::JobNegotiation(v) => {
                 Protocol::JobNegotiation.serialize(writer)? + v.serialize(writer)?
-            } // SetupConnection::TemplateDistribution(v) => {
-              //     Protocol::TemplateDistribution.serialize(writer)? + v.serialize(writer)?
-              // }
-              // SetupConnection::JobDistribution(v) => {
-              //     Protocol::JobDistribution.serialize(writer)? + v.serialize(writer)?
-              // }
+            }
+            SetupConnection::TemplateDistribution(v) => {
+                Protocol::TemplateDistribution.serialize(writer)? + v.serialize(writer)?
+            }
+            SetupConnection::JobDistribution(v) => {
+                Protocol::JobDistribution.serialize(writer)? + v.serialize(writer)?
+            }
         };
 
         Ok(length)
     }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            SetupConnection::Mining(v) => Protocol::Mining.encoded_len() + v.encoded_len(),
+            SetupConnection::JobNegotiation(v) => {
+                Protocol::JobNegotiation.encoded_len() + v.encoded_len()
+            }
+            SetupConnection::TemplateDistribution(v) => {
+                Protocol::TemplateDistribution.encoded_len() + v.encoded_len()
+            }
+            SetupConnection::JobDistribution(v) => {
+                Protocol::JobDistribution.encoded_len() + v.encoded_len()
+            }
+        }
+    }
 }
 
 impl Deserializable for SetupConnection {
     fn deserialize(parser: &mut ByteParser) -> Result<SetupConnection> {
-        let protocol = Protocol::deserialize(parser)?;
+        let protocol = deserialize_field::<Protocol>(parser, "protocol")?;
         let variant = match protocol {
             Protocol::Mining => {
                 SetupConnection::Mining(mining::SetupConnection::deserialize(parser)?)
@@ -207,13 +334,12 @@ impl Deserializable for SetupConnection {
             Protocol::JobNegotiation => SetupConnection::JobNegotiation(
                 job_negotiation::SetupConnection::deserialize(parser)?,
             ),
-            _ => return Err(Error::Unimplemented()),
-            // Protocol::TemplateDistribution => SetupConnection::TemplateDistribution(
-            //     template_distribution::SetupConnection::deserialize(parser)?,
-            // ),
-            // Protocol::JobDistribution => SetupConnection::JobDistribution(
-            //     job_distribution::SetupConnection::deserialize(parser)?,
-            // ),
+            Protocol::TemplateDistribution => SetupConnection::TemplateDistribution(
+                template_distribution::SetupConnection::deserialize(parser)?,
+            ),
+            Protocol::JobDistribution => SetupConnection::JobDistribution(
+                job_distribution::SetupConnection::deserialize(parser)?,
+            ),
         };
 
         Ok(variant)
@@ -298,6 +424,12 @@ macro_rules! impl_setup_connection_tests {
             assert!(default_setup_conn(false, args).is_err());
         }
 
+        #[test]
+        fn protocol() {
+            let conn = default_setup_conn(false, HashMap::new()).unwrap();
+            assert_eq!(conn.protocol(), $protocol);
+        }
+
         #[test]
         fn serialize() {
             let conn = default_setup_conn(false, HashMap::new()).unwrap();
@@ -305,9 +437,10 @@ macro_rules! impl_setup_connection_tests {
 
             // Check the serialized connection is the correct length.
             assert_eq!(result.len(), 75);
+            assert_eq!(conn.encoded_len(), 75);
 
             // Check the protocol byte was serialized correctly.
-            assert_eq!(result[0], $protocol.into());
+            assert_eq!(result[0], u8::from($protocol));
 
             // Check the flags were serialized correctly.
             assert_eq!(result[5..9], codec::serialize(&$flags::all()).unwrap());
@@ -372,3 +505,27 @@ mod job_negotiation_setup_connection_tests {
         SetupConnectionFlags
     );
 }
+
+#[cfg(test)]
+mod template_distribution_setup_connection_tests {
+    use super::*;
+    use crate::template_distribution::SetupConnectionFlags;
+
+    impl_setup_connection_tests!(
+        Protocol::TemplateDistribution,
+        SetupConnection::new_template_distribution,
+        SetupConnectionFlags
+    );
+}
+
+#[cfg(test)]
+mod job_distribution_setup_connection_tests {
+    use super::*;
+    use crate::job_distribution::SetupConnectionFlags;
+
+    impl_setup_connection_tests!(
+        Protocol::JobDistribution,
+        SetupConnection::new_job_distribution,
+        SetupConnectionFlags
+    );
+}
@@ -0,0 +1,69 @@
+use crate::{
+    common::SetupConnectionErrorCode,
+    error::{Error, Result},
+    impl_message,
+};
+
+impl_message!(
+    /// SetupConnectionError is the response to a rejected SetupConnection
+    /// message when the sub protocol of the connection is not known, for
+    /// example when decoding an [AnyMessage](../codec/enum.AnyMessage.html).
+    ///
+    /// The flags are the raw bits of the SetupConnectionFlags of the sub
+    /// protocol. The message can be converted into the SetupConnectionError of
+    /// a sub protocol using TryFrom, which verifies the flags.
+    SetupConnectionError,
+
+    /// The raw bits of the feature flags of the sub protocol that the server
+    /// does NOT support.
+    flags u32,
+
+    /// Error code is a predefined STR0_255 error code.
+    error_code SetupConnectionErrorCode
+);
+
+impl SetupConnectionError {
+    pub fn new(flags: u32, error_code: SetupConnectionErrorCode) -> Result<SetupConnectionError> {
+        if flags == 0 && error_code == SetupConnectionErrorCode::UnsupportedFeatureFlags {
+            return Err(Error::RequirementError(
+                "a full set of unsupported flags MUST be returned to the client".into(),
+            ));
+        }
+
+        Ok(SetupConnectionError { flags, error_code })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_setup_connection_error() -> SetupConnectionError {
+        SetupConnectionError::new(0x01, SetupConnectionErrorCode::UnsupportedFeatureFlags).unwrap()
+    }
+
+    fn make_serialized_setup_connection_error() -> Vec<u8> {
+        let mut serialized = vec![
+            0x01, 0x00, 0x00, 0x00, // flags
+        ];
+        serialized.push(25); // error_code length
+        serialized.extend("unsupported-feature-flags".as_bytes()); // error_code
+
+        serialized
+    }
+
+    impl_message_tests!(
+        SetupConnectionError,
+        make_serialized_setup_connection_error,
+        make_deserialized_setup_connection_error
+    );
+
+    #[test]
+    fn new_requires_unsupported_flags() {
+        assert!(matches!(
+            SetupConnectionError::new(0, SetupConnectionErrorCode::UnsupportedFeatureFlags),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{error::Result, impl_message};
+
+impl_message!(
+    /// SetupConnectionSuccess is the response to a SetupConnection message
+    /// when the sub protocol of the connection is not known, for example when
+    /// decoding an [AnyMessage](../codec/enum.AnyMessage.html).
+    ///
+    /// The flags are the raw bits of the SetupConnectionSuccessFlags of the
+    /// sub protocol. The message can be converted into the SetupConnectionSuccess
+    /// of a sub protocol using TryFrom, which verifies the flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::convert::TryFrom;
+    /// use stratumv2::{common, mining};
+    ///
+    /// let conn_success = common::SetupConnectionSuccess::new(2, 0x01).unwrap();
+    /// let mining_conn_success = mining::SetupConnectionSuccess::try_from(conn_success).unwrap();
+    ///
+    /// assert_eq!(
+    ///     mining_conn_success.flags,
+    ///     mining::SetupConnectionSuccessFlags::REQUIRES_FIXED_VERSION
+    /// );
+    /// ```
+    SetupConnectionSuccess,
+
+    /// Version proposed by the connecting node as one of the verions supported
+    /// by the upstream node.
+    used_version u16,
+
+    /// The raw bits of the feature flags of the sub protocol.
+    flags u32
+);
+
+impl SetupConnectionSuccess {
+    pub fn new(used_version: u16, flags: u32) -> Result<SetupConnectionSuccess> {
+        Ok(SetupConnectionSuccess {
+            used_version,
+            flags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_setup_connection_success() -> SetupConnectionSuccess {
+        SetupConnectionSuccess::new(2, 0x03).unwrap()
+    }
+
+    fn make_serialized_setup_connection_success() -> Vec<u8> {
+        return vec![
+            0x02, 0x00, // used_version
+            0x03, 0x00, 0x00, 0x00, // flags
+        ];
+    }
+
+    impl_message_tests!(
+        SetupConnectionSuccess,
+        make_serialized_setup_connection_success,
+        make_deserialized_setup_connection_success
+    );
+
+    #[test]
+    fn try_from_unknown_flags() {
+        use crate::error::Error;
+        use crate::template_distribution;
+        use core::convert::TryFrom;
+
+        let conn_success = SetupConnectionSuccess::new(2, 0x01).unwrap();
+        assert!(matches!(
+            template_distribution::SetupConnectionSuccess::try_from(conn_success),
+            Err(Error::UnknownFlags())
+        ));
+    }
+}
@@ -1,22 +1,25 @@
+use crate::prelude::*;
 use thiserror::Error;
 
 /// The main error type for this library.
 #[derive(Error, Debug)]
 pub enum Error {
+    #[cfg(feature = "std")]
     #[error(transparent)]
     Base58Error(#[from] bitcoin::util::base58::Error),
 
     #[error("`{0}`")]
     VersionError(String),
 
+    #[cfg(feature = "std")]
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 
     #[error(transparent)]
-    Utf8Error(#[from] std::str::Utf8Error),
+    Utf8Error(#[from] core::str::Utf8Error),
 
     #[error(transparent)]
-    FromUtf8Error(#[from] std::string::FromUtf8Error),
+    FromUtf8Error(#[from] alloc::string::FromUtf8Error),
 
     #[error("`{0}`")]
     ProtocolMismatchError(String),
@@ -30,14 +33,25 @@ pub enum Error {
     #[error("`{0}`")]
     ParseError(String),
 
+    #[error("expected `{1}` bytes at offset `{0}` but only `{2}` remain")]
+    OutOfBounds(usize, usize, usize),
+
+    #[error("`{1}` unexpected trailing bytes at offset `{0}`")]
+    TrailingBytes(usize, usize),
+
+    #[error("failed to deserialize `{0}` at offset `{1}`: {2}")]
+    FieldError(&'static str, usize, Box<Error>),
+
+    #[cfg(feature = "std")]
     #[error(transparent)]
     AuthorityKeyError(#[from] ed25519_dalek::ed25519::Error),
 
+    #[cfg(feature = "std")]
     #[error(transparent)]
     SystemTimeError(#[from] std::time::SystemTimeError),
 
     #[error(transparent)]
-    TryFromSliceError(#[from] std::array::TryFromSliceError),
+    TryFromSliceError(#[from] core::array::TryFromSliceError),
 
     #[error("Unimplemented")]
     Unimplemented(),
@@ -54,12 +68,22 @@ pub enum Error {
     #[error("parsed message type `{0}/{1}` does not match expected message")]
     UnexpectedMessageType(u16, u8),
 
+    #[error("the buffer of `{0}` bytes is too small to serialize `{1}` bytes")]
+    BufferTooSmall(usize, usize),
+
     #[error("parsed channel bit `{0}` does not match expected message")]
     UnexpectedChannelBit(bool),
 
+    #[error("the channel id `{0}` is unknown")]
+    UnknownChannelId(u32),
+
+    #[error("the group channel id `{0}` is unknown")]
+    UnknownGroupChannelId(u32),
+
+    #[cfg(feature = "std")]
     #[error(transparent)]
     NoiseError(#[from] noiseexplorer_nx::error::NoiseError),
 }
 
 /// Alias Result type for the library.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
@@ -0,0 +1,15 @@
+//! The sub protocol is used to pass newly negotiated mining jobs from a Job
+//! Negotiator to Mining Proxies or Mining Devices. If miners are not choosing
+//! their transaction sets, jobs are distributed from the Mining Pool directly
+//! to the Mining Proxies/Devices instead.
+//!
+//! The specification only defines the SetupConnection messages for this
+//! protocol, the job distribution messages themselves are yet to be specified.
+
+mod setup_connection;
+mod setup_connection_error;
+mod setup_connection_success;
+
+pub use setup_connection::{SetupConnection, SetupConnectionFlags};
+pub use setup_connection_error::SetupConnectionError;
+pub use setup_connection_success::{SetupConnectionSuccess, SetupConnectionSuccessFlags};
@@ -0,0 +1,54 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, STR0_255};
+
+impl_message!(
+    /// AllocateMiningJobToken is sent from the Client (Job Negotiator) to the
+    /// Server (Mining Pool) to request an identifier for a future mining job.
+    AllocateMiningJobToken,
+
+    /// Unconstrained sequence of bytes. Whatever is needed by the Server to
+    /// identify/authenticate the Client, e.g. "braiinstest". Additional
+    /// restrictions can be imposed by the Server.
+    user_identifier STR0_255,
+
+    /// A Client-specified identifier for pairing responses.
+    request_id u32
+);
+
+impl AllocateMiningJobToken {
+    pub fn new<T: Into<String>>(
+        user_identifier: T,
+        request_id: u32,
+    ) -> Result<AllocateMiningJobToken> {
+        Ok(AllocateMiningJobToken {
+            user_identifier: STR0_255::new(user_identifier)?,
+            request_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_allocate_mining_job_token() -> AllocateMiningJobToken {
+        AllocateMiningJobToken::new("braiinstest", 1u32).unwrap()
+    }
+
+    fn make_serialized_allocate_mining_job_token() -> Vec<u8> {
+        return vec![
+            0x0b, 0x62, 0x72, 0x61, 0x69, 0x69, 0x6e, 0x73, 0x74, 0x65, 0x73,
+            0x74, // user_identifier
+            0x01, 0x00, 0x00, 0x00, // request_id
+        ];
+    }
+
+    impl_message_tests!(
+        AllocateMiningJobToken,
+        make_serialized_allocate_mining_job_token,
+        make_deserialized_allocate_mining_job_token
+    );
+}
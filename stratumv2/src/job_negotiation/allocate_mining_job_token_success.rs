@@ -0,0 +1,78 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_255, B0_64K};
+
+impl_message!(
+    /// AllocateMiningJobTokenSuccess is sent from the Server to the Client in
+    /// response to an [AllocateMiningJobToken](struct.AllocateMiningJobToken.html)
+    /// message.
+    AllocateMiningJobTokenSuccess,
+
+    /// The request_id received in the AllocateMiningJobToken message.
+    request_id u32,
+
+    /// A token that makes the Client eligible for committing a mining job for
+    /// approval/transaction negotiation or for identifying custom mining job on
+    /// the mining connection.
+    mining_job_token B0_255,
+
+    /// The maximum additional size of coinbase transaction outputs the Client
+    /// may add, in bytes.
+    coinbase_output_max_additional_size u32,
+
+    /// Serialized bitcoin transaction outputs which the Server requires to be
+    /// included in the coinbase transaction of the committed job.
+    coinbase_output B0_64K,
+
+    /// If true, the mining_job_token can be used immediately on a mining
+    /// connection in the SetCustomMiningJob message, even before the
+    /// CommitMiningJob and CommitMiningJobSuccess messages have been sent and
+    /// received. If false, the Client MUST wait for CommitMiningJobSuccess
+    /// before using the token.
+    async_mining_allowed bool
+);
+
+impl AllocateMiningJobTokenSuccess {
+    pub fn new<T: Into<Vec<u8>>, U: Into<Vec<u8>>>(
+        request_id: u32,
+        mining_job_token: T,
+        coinbase_output_max_additional_size: u32,
+        coinbase_output: U,
+        async_mining_allowed: bool,
+    ) -> Result<AllocateMiningJobTokenSuccess> {
+        Ok(AllocateMiningJobTokenSuccess {
+            request_id,
+            mining_job_token: B0_255::new(mining_job_token)?,
+            coinbase_output_max_additional_size,
+            coinbase_output: B0_64K::new(coinbase_output)?,
+            async_mining_allowed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_allocate_mining_job_token_success() -> AllocateMiningJobTokenSuccess {
+        AllocateMiningJobTokenSuccess::new(1u32, [2u8; 2], 3u32, [4u8; 2], true).unwrap()
+    }
+
+    fn make_serialized_allocate_mining_job_token_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x02, 0x02, 0x02, // mining_job_token
+            0x03, 0x00, 0x00, 0x00, // coinbase_output_max_additional_size
+            0x02, 0x00, 0x04, 0x04, // coinbase_output
+            0x01, // async_mining_allowed
+        ];
+    }
+
+    impl_message_tests!(
+        AllocateMiningJobTokenSuccess,
+        make_serialized_allocate_mining_job_token_success,
+        make_deserialized_allocate_mining_job_token_success
+    );
+}
@@ -0,0 +1,163 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, ShortTxId, B0_255, B0_64K, SEQ0_64K, U256};
+
+impl_message!(
+    /// CommitMiningJob is sent from the Client (Job Negotiator) to the Server
+    /// (Mining Pool) to propose a mining job for approval. The Server either
+    /// accepts it with a [CommitMiningJobSuccess](struct.CommitMiningJobSuccess.html)
+    /// or rejects it with a [CommitMiningJobError](struct.CommitMiningJobError.html).
+    ///
+    /// Later revisions of the specification rename this message to
+    /// DeclareMiningJob.
+    CommitMiningJob,
+
+    /// A Client-specified identifier for pairing responses.
+    request_id u32,
+
+    /// A token received from the Server in AllocateMiningJobTokenSuccess.
+    mining_job_token B0_255,
+
+    /// A valid header version field that reflects the current network
+    /// consensus. The general purpose bits (as specified in BIP320) can be
+    /// freely manipulated by the downstream node.
+    version u32,
+
+    /// The coinbase transaction nVersion field.
+    coinbase_tx_version u32,
+
+    /// Up to 8 bytes (not including the length byte) which are to be placed at
+    /// the beginning of the coinbase field in the coinbase transaction.
+    coinbase_prefix B0_255,
+
+    /// The coinbase transaction input's nSequence field.
+    coinbase_tx_input_nsequence u32,
+
+    /// The value, in satoshis, available for spending in coinbase outputs added
+    /// by the Client. Includes both transaction fees and block subsidy.
+    coinbase_tx_value_remaining u64,
+
+    /// All the outputs that will be included in the coinbase transaction.
+    coinbase_tx_outputs B0_64K,
+
+    /// The locktime field in the coinbase transaction.
+    coinbase_tx_locktime u32,
+
+    /// The extranonce size requested to be always available for the mining
+    /// channel when this job is used on a mining connection.
+    min_extranonce_size u16,
+
+    /// A unique nonce used to ensure tx_short_hash collisions are uncorrelated
+    /// across the network.
+    tx_short_hash_nonce u64,
+
+    /// Sequence of the short transaction ids (BIP152) of the transactions in
+    /// the block template, excluding the coinbase transaction.
+    tx_short_hash_list SEQ0_64K<ShortTxId>,
+
+    /// Hash of the full sequence of SHA256(transaction_data) contained in
+    /// tx_short_hash_list.
+    tx_hash_list_hash U256,
+
+    /// Extra data which the Pool may require to validate the work.
+    excess_data B0_64K
+);
+
+impl CommitMiningJob {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<
+        T: Into<Vec<u8>>,
+        U: Into<Vec<u8>>,
+        V: Into<Vec<u8>>,
+        W: Into<Vec<ShortTxId>>,
+        X: Into<U256>,
+        Y: Into<Vec<u8>>,
+    >(
+        request_id: u32,
+        mining_job_token: T,
+        version: u32,
+        coinbase_tx_version: u32,
+        coinbase_prefix: U,
+        coinbase_tx_input_nsequence: u32,
+        coinbase_tx_value_remaining: u64,
+        coinbase_tx_outputs: V,
+        coinbase_tx_locktime: u32,
+        min_extranonce_size: u16,
+        tx_short_hash_nonce: u64,
+        tx_short_hash_list: W,
+        tx_hash_list_hash: X,
+        excess_data: Y,
+    ) -> Result<CommitMiningJob> {
+        Ok(CommitMiningJob {
+            request_id,
+            mining_job_token: B0_255::new(mining_job_token)?,
+            version,
+            coinbase_tx_version,
+            coinbase_prefix: B0_255::new(coinbase_prefix)?,
+            coinbase_tx_input_nsequence,
+            coinbase_tx_value_remaining,
+            coinbase_tx_outputs: B0_64K::new(coinbase_tx_outputs)?,
+            coinbase_tx_locktime,
+            min_extranonce_size,
+            tx_short_hash_nonce,
+            tx_short_hash_list: SEQ0_64K::new(tx_short_hash_list)?,
+            tx_hash_list_hash: tx_hash_list_hash.into(),
+            excess_data: B0_64K::new(excess_data)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_commit_mining_job() -> CommitMiningJob {
+        CommitMiningJob::new(
+            1u32,
+            [2u8; 2],
+            3u32,
+            4u32,
+            [5u8; 2],
+            6u32,
+            7u64,
+            [8u8; 2],
+            9u32,
+            10u16,
+            11u64,
+            vec![ShortTxId([12u8; 6])],
+            [13u8; 32],
+            [14u8; 2],
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_commit_mining_job() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x02, 0x02, 0x02, // mining_job_token
+            0x03, 0x00, 0x00, 0x00, // version
+            0x04, 0x00, 0x00, 0x00, // coinbase_tx_version
+            0x02, 0x05, 0x05, // coinbase_prefix
+            0x06, 0x00, 0x00, 0x00, // coinbase_tx_input_nsequence
+            0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // coinbase_tx_value_remaining
+            0x02, 0x00, 0x08, 0x08, // coinbase_tx_outputs
+            0x09, 0x00, 0x00, 0x00, // coinbase_tx_locktime
+            0x0a, 0x00, // min_extranonce_size
+            0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_short_hash_nonce
+            0x01, 0x00, // tx_short_hash_list length
+            0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, // tx_short_hash_list[0]
+            0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d,
+            0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d, 0x0d,
+            0x0d, 0x0d, 0x0d, 0x0d, // tx_hash_list_hash
+            0x02, 0x00, 0x0e, 0x0e, // excess_data
+        ];
+    }
+
+    impl_message_tests!(
+        CommitMiningJob,
+        make_serialized_commit_mining_job,
+        make_deserialized_commit_mining_job
+    );
+}
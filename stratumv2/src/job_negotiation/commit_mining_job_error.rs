@@ -0,0 +1,129 @@
+use crate::error::Result;
+use crate::impl_error_code_enum;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_64K};
+
+/// Contains the error codes for the [CommitMiningJobError](struct.CommitMiningJobError.html)
+/// message. Each error code has a default STR0_255 message.
+///
+/// The specification defines `invalid-job-param-value-{}` where `{}` is the
+/// name of the [CommitMiningJob](struct.CommitMiningJob.html) field with an
+/// invalid value. Each of those fields is represented by its own variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitMiningJobErrorCode {
+    /// The mining_job_token was not allocated by the Server or has expired.
+    InvalidMiningJobToken,
+
+    InvalidJobParamValueVersion,
+    InvalidJobParamValueCoinbaseTxVersion,
+    InvalidJobParamValueCoinbasePrefix,
+    InvalidJobParamValueCoinbaseTxInputNSequence,
+    InvalidJobParamValueCoinbaseTxValueRemaining,
+    InvalidJobParamValueCoinbaseTxOutputs,
+    InvalidJobParamValueCoinbaseTxLocktime,
+    InvalidJobParamValueMinExtranonceSize,
+    InvalidJobParamValueTxShortHashNonce,
+    InvalidJobParamValueTxShortHashList,
+    InvalidJobParamValueTxHashListHash,
+    InvalidJobParamValueExcessData,
+}
+
+impl_error_code_enum!(
+    CommitMiningJobErrorCode,
+    CommitMiningJobErrorCode::InvalidMiningJobToken => "invalid-mining-job-token",
+    CommitMiningJobErrorCode::InvalidJobParamValueVersion => "invalid-job-param-value-version",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxVersion => "invalid-job-param-value-coinbase_tx_version",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbasePrefix => "invalid-job-param-value-coinbase_prefix",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxInputNSequence => "invalid-job-param-value-coinbase_tx_input_nSequence",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxValueRemaining => "invalid-job-param-value-coinbase_tx_value_remaining",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs => "invalid-job-param-value-coinbase_tx_outputs",
+    CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxLocktime => "invalid-job-param-value-coinbase_tx_locktime",
+    CommitMiningJobErrorCode::InvalidJobParamValueMinExtranonceSize => "invalid-job-param-value-min_extranonce_size",
+    CommitMiningJobErrorCode::InvalidJobParamValueTxShortHashNonce => "invalid-job-param-value-tx_short_hash_nonce",
+    CommitMiningJobErrorCode::InvalidJobParamValueTxShortHashList => "invalid-job-param-value-tx_short_hash_list",
+    CommitMiningJobErrorCode::InvalidJobParamValueTxHashListHash => "invalid-job-param-value-tx_hash_list_hash",
+    CommitMiningJobErrorCode::InvalidJobParamValueExcessData => "invalid-job-param-value-excess_data"
+);
+
+impl_message!(
+    /// CommitMiningJobError is sent from the Server to the Client when a
+    /// [CommitMiningJob](struct.CommitMiningJob.html) has been rejected.
+    CommitMiningJobError,
+
+    /// The request_id received in the CommitMiningJob message.
+    request_id u32,
+
+    /// Pre-determined human readable error codes for the CommitMiningJob message.
+    error_code CommitMiningJobErrorCode,
+
+    /// Optional data providing further details about the error.
+    error_details B0_64K
+);
+
+impl CommitMiningJobError {
+    pub fn new<T: Into<Vec<u8>>>(
+        request_id: u32,
+        error_code: CommitMiningJobErrorCode,
+        error_details: T,
+    ) -> Result<CommitMiningJobError> {
+        Ok(CommitMiningJobError {
+            request_id,
+            error_code,
+            error_details: B0_64K::new(error_details)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_commit_mining_job_error() -> CommitMiningJobError {
+        CommitMiningJobError::new(
+            1u32,
+            CommitMiningJobErrorCode::InvalidJobParamValueVersion,
+            [2u8; 2],
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_commit_mining_job_error() -> Vec<u8> {
+        let mut serialized = vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+        ];
+        serialized.push(31); // error_code length
+        serialized.extend("invalid-job-param-value-version".as_bytes()); // error_code
+        serialized.extend(vec![0x02, 0x00, 0x02, 0x02]); // error_details
+
+        serialized
+    }
+
+    impl_message_tests!(
+        CommitMiningJobError,
+        make_serialized_commit_mining_job_error,
+        make_deserialized_commit_mining_job_error
+    );
+
+    #[test]
+    fn error_code_from_str() {
+        assert_eq!(
+            "invalid-mining-job-token"
+                .parse::<CommitMiningJobErrorCode>()
+                .unwrap(),
+            CommitMiningJobErrorCode::InvalidMiningJobToken
+        );
+        assert_eq!(
+            "invalid-job-param-value-tx_short_hash_list"
+                .parse::<CommitMiningJobErrorCode>()
+                .unwrap(),
+            CommitMiningJobErrorCode::InvalidJobParamValueTxShortHashList
+        );
+        assert!(matches!(
+            "invalid-job-param-value-unknown".parse::<CommitMiningJobErrorCode>(),
+            Err(Error::UnknownErrorCode { .. })
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_255};
+
+impl_message!(
+    /// CommitMiningJobSuccess is sent from the Server to the Client when a
+    /// [CommitMiningJob](struct.CommitMiningJob.html) has been accepted.
+    CommitMiningJobSuccess,
+
+    /// The request_id received in the CommitMiningJob message.
+    request_id u32,
+
+    /// Unique identifier provided by the pool of the job that the Job
+    /// Negotiator has committed with the pool. The Client MUST use this token
+    /// in the SetCustomMiningJob message on the mining connection.
+    new_mining_job_token B0_255
+);
+
+impl CommitMiningJobSuccess {
+    pub fn new<T: Into<Vec<u8>>>(
+        request_id: u32,
+        new_mining_job_token: T,
+    ) -> Result<CommitMiningJobSuccess> {
+        Ok(CommitMiningJobSuccess {
+            request_id,
+            new_mining_job_token: B0_255::new(new_mining_job_token)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_commit_mining_job_success() -> CommitMiningJobSuccess {
+        CommitMiningJobSuccess::new(1u32, [2u8; 2]).unwrap()
+    }
+
+    fn make_serialized_commit_mining_job_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x02, 0x02, 0x02, // new_mining_job_token
+        ];
+    }
+
+    impl_message_tests!(
+        CommitMiningJobSuccess,
+        make_serialized_commit_mining_job_success,
+        make_deserialized_commit_mining_job_success
+    );
+}
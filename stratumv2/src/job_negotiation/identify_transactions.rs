@@ -0,0 +1,43 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// IdentifyTransactions is sent from the Server to the Client to ask for
+    /// the full list of transaction data hashes of a committed mining job,
+    /// usually because the Server failed to resolve some of the short
+    /// transaction ids.
+    IdentifyTransactions,
+
+    /// Unique identifier for pairing the response to the CommitMiningJob
+    /// message.
+    request_id u32
+);
+
+impl IdentifyTransactions {
+    pub fn new(request_id: u32) -> Result<IdentifyTransactions> {
+        Ok(IdentifyTransactions { request_id })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_identify_transactions() -> IdentifyTransactions {
+        IdentifyTransactions::new(1u32).unwrap()
+    }
+
+    fn make_serialized_identify_transactions() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+        ];
+    }
+
+    impl_message_tests!(
+        IdentifyTransactions,
+        make_serialized_identify_transactions,
+        make_deserialized_identify_transactions
+    );
+}
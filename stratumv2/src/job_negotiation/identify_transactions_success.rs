@@ -0,0 +1,56 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, SEQ0_64K, U256};
+
+impl_message!(
+    /// IdentifyTransactionsSuccess is sent from the Client to the Server in
+    /// response to an [IdentifyTransactions](struct.IdentifyTransactions.html)
+    /// message.
+    IdentifyTransactionsSuccess,
+
+    /// The request_id received in the IdentifyTransactions message.
+    request_id u32,
+
+    /// The full list of transaction data hashes used to build the mining job
+    /// in the corresponding CommitMiningJob message.
+    tx_data_hashes SEQ0_64K<U256>
+);
+
+impl IdentifyTransactionsSuccess {
+    pub fn new<T: Into<Vec<U256>>>(
+        request_id: u32,
+        tx_data_hashes: T,
+    ) -> Result<IdentifyTransactionsSuccess> {
+        Ok(IdentifyTransactionsSuccess {
+            request_id,
+            tx_data_hashes: SEQ0_64K::new(tx_data_hashes)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_identify_transactions_success() -> IdentifyTransactionsSuccess {
+        IdentifyTransactionsSuccess::new(1u32, vec![U256([2u8; 32])]).unwrap()
+    }
+
+    fn make_serialized_identify_transactions_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x01, 0x00, // tx_data_hashes length
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, // tx_data_hashes[0]
+        ];
+    }
+
+    impl_message_tests!(
+        IdentifyTransactionsSuccess,
+        make_serialized_identify_transactions_success,
+        make_deserialized_identify_transactions_success
+    );
+}
@@ -7,10 +7,28 @@
 //! nodes (Mining Farms/Devices) can use the same negotiation result on all
 //! their connections.
 
+mod allocate_mining_job_token;
+mod allocate_mining_job_token_success;
+mod commit_mining_job;
+mod commit_mining_job_error;
+mod commit_mining_job_success;
+mod identify_transactions;
+mod identify_transactions_success;
+mod provide_missing_transactions;
+mod provide_missing_transactions_success;
 mod setup_connection;
 mod setup_connection_error;
 mod setup_connection_success;
 
+pub use allocate_mining_job_token::AllocateMiningJobToken;
+pub use allocate_mining_job_token_success::AllocateMiningJobTokenSuccess;
+pub use commit_mining_job::CommitMiningJob;
+pub use commit_mining_job_error::{CommitMiningJobError, CommitMiningJobErrorCode};
+pub use commit_mining_job_success::CommitMiningJobSuccess;
+pub use identify_transactions::IdentifyTransactions;
+pub use identify_transactions_success::IdentifyTransactionsSuccess;
+pub use provide_missing_transactions::ProvideMissingTransactions;
+pub use provide_missing_transactions_success::ProvideMissingTransactionsSuccess;
 pub use setup_connection::{SetupConnection, SetupConnectionFlags};
 pub use setup_connection_error::SetupConnectionError;
 pub use setup_connection_success::{SetupConnectionSuccess, SetupConnectionSuccessFlags};
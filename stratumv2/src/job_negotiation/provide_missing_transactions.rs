@@ -0,0 +1,57 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, SEQ0_64K};
+
+impl_message!(
+    /// ProvideMissingTransactions is sent from the Server to the Client when
+    /// the Server is unable to resolve some of the short transaction ids of a
+    /// committed mining job, for example because the transactions are not in
+    /// its mempool.
+    ProvideMissingTransactions,
+
+    /// Identifier of the original CommitMiningJob request.
+    request_id u32,
+
+    /// A list of unrecognized transactions that need to be supplied by the
+    /// Client in full. They are identified by their position in the
+    /// tx_short_hash_list of the original CommitMiningJob message.
+    unknown_tx_position_list SEQ0_64K<u16>
+);
+
+impl ProvideMissingTransactions {
+    pub fn new<T: Into<Vec<u16>>>(
+        request_id: u32,
+        unknown_tx_position_list: T,
+    ) -> Result<ProvideMissingTransactions> {
+        Ok(ProvideMissingTransactions {
+            request_id,
+            unknown_tx_position_list: SEQ0_64K::new(unknown_tx_position_list)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_provide_missing_transactions() -> ProvideMissingTransactions {
+        ProvideMissingTransactions::new(1u32, vec![2u16, 3u16]).unwrap()
+    }
+
+    fn make_serialized_provide_missing_transactions() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x02, 0x00, // unknown_tx_position_list length
+            0x02, 0x00, // unknown_tx_position_list[0]
+            0x03, 0x00, // unknown_tx_position_list[1]
+        ];
+    }
+
+    impl_message_tests!(
+        ProvideMissingTransactions,
+        make_serialized_provide_missing_transactions,
+        make_deserialized_provide_missing_transactions
+    );
+}
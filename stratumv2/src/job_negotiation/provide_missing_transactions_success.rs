@@ -0,0 +1,55 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_16M, SEQ0_64K};
+
+impl_message!(
+    /// ProvideMissingTransactionsSuccess is sent from the Client to the Server
+    /// in response to a [ProvideMissingTransactions](struct.ProvideMissingTransactions.html)
+    /// message.
+    ProvideMissingTransactionsSuccess,
+
+    /// The request_id received in the ProvideMissingTransactions message.
+    request_id u32,
+
+    /// The full serialized transactions, in the order requested by the
+    /// unknown_tx_position_list.
+    transaction_list SEQ0_64K<B0_16M>
+);
+
+impl ProvideMissingTransactionsSuccess {
+    pub fn new<T: Into<Vec<B0_16M>>>(
+        request_id: u32,
+        transaction_list: T,
+    ) -> Result<ProvideMissingTransactionsSuccess> {
+        Ok(ProvideMissingTransactionsSuccess {
+            request_id,
+            transaction_list: SEQ0_64K::new(transaction_list)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_provide_missing_transactions_success() -> ProvideMissingTransactionsSuccess
+    {
+        ProvideMissingTransactionsSuccess::new(1u32, vec![B0_16M::new([2u8; 2]).unwrap()]).unwrap()
+    }
+
+    fn make_serialized_provide_missing_transactions_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // request_id
+            0x01, 0x00, // transaction_list length
+            0x02, 0x00, 0x00, 0x02, 0x02, // transaction_list[0]
+        ];
+    }
+
+    impl_message_tests!(
+        ProvideMissingTransactionsSuccess,
+        make_serialized_provide_missing_transactions_success,
+        make_deserialized_provide_missing_transactions_success
+    );
+}
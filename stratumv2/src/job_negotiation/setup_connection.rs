@@ -12,7 +12,7 @@ bitflags!(
   }
 );
 
-impl_bitflags_serde!(SetupConnectionFlags);
+impl_bitflags_serde!(SetupConnectionFlags, REQUIRES_ASYNC_JOB_MINING);
 impl_setup_connection!(SetupConnectionFlags);
 
 #[cfg(test)]
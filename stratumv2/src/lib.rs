@@ -3,19 +3,35 @@
 //! Stratum V2 sources:
 //! - [Stratum V2 Overview](https://braiins.com/stratum-v2)
 //! - [Stratum V2 Specification](https://docs.google.com/document/d/1FadCWj-57dvhxsnFM_7X806qyvhR0u3i85607bGHxvg/edit)
+//!
+//! The codec, types and message definitions can be used in `no_std`
+//! environments with `alloc` by disabling the default `std` feature. The
+//! network and noise modules require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+#[macro_use]
+extern crate alloc;
 #[macro_use]
 extern crate bitflags;
 extern crate thiserror;
 
+#[cfg(feature = "std")]
 pub extern crate bitcoin;
 
+// Allows the derive macros, which refer to `::stratumv2`, to be used inside
+// this crate.
+extern crate self as stratumv2;
+
 /// Common messages and flags for all sub protocols.
 pub mod common;
 
 /// Errors returned in the library.
 pub mod error;
 
+/// Job Distribution is a sub protocol of Stratum V2.
+pub mod job_distribution;
+
 /// Job Negotiation is a sub protocol of Stratum V2.
 pub mod job_negotiation;
 mod macro_message;
@@ -23,6 +39,9 @@ mod macro_message;
 /// Mining is the main sub protocol of Stratum V2.
 pub mod mining;
 
+/// Template Distribution is a sub protocol of Stratum V2.
+pub mod template_distribution;
+
 /// Codec contains all the functionality to serialize, deserialize and frame network messages.
 /// This also includes the required Serializable, Deserializable and Frameable traits.
 pub mod codec;
@@ -34,7 +53,18 @@ pub mod types;
 /// Handshake, creating a symmetric key to perform secure communication.
 /// This module contains functions to verify and generate signatures
 /// for both Client and Server to attest to the authenticty of an Upstream Node.
+#[cfg(feature = "std")]
 pub mod noise;
 
 /// Structs and Traits required for a networked implementation
+#[cfg(feature = "std")]
 pub mod network;
+
+/// The alloc types used in place of the std prelude, so that the codec, types
+/// and message definitions can be built without std.
+mod prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+}
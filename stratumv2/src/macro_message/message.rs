@@ -1,11 +1,17 @@
 pub mod macro_prelude {
-    pub use crate::codec::{ByteParser, Deserializable, Frameable, Serializable};
+    pub use crate::codec::{
+        deserialize_field, io, ByteParser, Deserializable, Frameable, Serializable,
+    };
     pub use crate::error::Result;
     pub use crate::types::MessageType;
-    pub use std::io;
 }
 
 /// Internal macro to build all the common requirements for a Stratum-v2 message.
+///
+/// The MessageType of the message defaults to the MessageType variant with the
+/// same name as the struct. Messages that share a name with a message in
+/// another sub protocol can specify the MessageType variant explicitly using
+/// `StructName => MessageType::Variant`.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_message {
@@ -13,13 +19,28 @@ macro_rules! impl_message {
       $(#[$doc_comment:meta])*
       $struct_name:ident,
       $($(#[$field_comment:meta])*
-      $field: ident $field_type:ident),*
+      $field: ident $field_type:ty),*
+    ) => {
+    $crate::impl_message!(
+      $(#[$doc_comment])*
+      $struct_name => MessageType::$struct_name,
+      $($(#[$field_comment])*
+      $field $field_type),*
+    );
+  };
+
+    (
+      $(#[$doc_comment:meta])*
+      $struct_name:ident => $message_type:path,
+      $($(#[$field_comment:meta])*
+      $field: ident $field_type:ty),*
     ) => {
     #[allow(unused_imports)]
     use crate::macro_message::message::macro_prelude::*;
 
     $(#[$doc_comment])*
     #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct $struct_name {
       $(
         $(#[$field_comment])*
@@ -35,19 +56,48 @@ macro_rules! impl_message {
         .iter()
         .sum())
       }
+
+      fn encoded_len(&self) -> usize {
+        [
+          $(self.$field.encoded_len(),)*
+        ]
+        .iter()
+        .sum()
+      }
     }
 
     impl Deserializable for $struct_name {
       fn deserialize(parser: &mut ByteParser) -> Result<Self> {
         $struct_name::new(
-          $($field_type::deserialize(parser)?,)*
+          $(deserialize_field::<$field_type>(parser, stringify!($field))?,)*
         )
       }
     }
 
     impl Frameable for $struct_name {
       fn message_type() -> MessageType {
-        MessageType::$struct_name
+        $message_type
+      }
+    }
+
+    // Deserializing with serde uses the constructor, so the same requirements
+    // are enforced as when deserializing from bytes.
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for $struct_name {
+      fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+      ) -> core::result::Result<$struct_name, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Fields {
+          $($field: $field_type),*
+        }
+
+        let fields = <Fields as serde::Deserialize>::deserialize(deserializer)?;
+        $struct_name::new(
+          $(fields.$field,)*
+        )
+        .map_err(serde::de::Error::custom)
       }
     }
   };
@@ -55,7 +105,9 @@ macro_rules! impl_message {
 
 #[cfg(test)]
 pub mod test_macro_prelude {
-    pub use crate::codec::{deserialize, frame, serialize, unframe, Frameable, Message};
+    pub use crate::codec::{
+        deserialize, frame, serialize, unframe, Frameable, Message, Serializable,
+    };
 }
 
 #[cfg(test)]
@@ -63,6 +115,14 @@ pub mod test_macro_prelude {
 #[macro_export]
 macro_rules! impl_message_tests {
     ($struct_name:ident, $make_serialized:ident, $make_deserialized:ident) => {
+        $crate::impl_message_tests!(
+            $struct_name => MessageType::$struct_name,
+            $make_serialized,
+            $make_deserialized
+        );
+    };
+
+    ($struct_name:ident => $message_type:path, $make_serialized:ident, $make_deserialized:ident) => {
         #[allow(unused_imports)]
         use crate::macro_message::message::test_macro_prelude::*;
 
@@ -75,16 +135,30 @@ macro_rules! impl_message_tests {
             let deserialized = $make_deserialized();
             let serialized = $make_serialized();
             assert_eq!(serialize(&deserialized).unwrap(), serialized);
+            assert_eq!(deserialized.encoded_len(), serialized.len());
             assert_eq!(
                 deserialize::<$struct_name>(serialized.as_slice()).unwrap(),
                 deserialized
             );
         }
 
+        #[cfg(feature = "serde")]
+        #[test]
+        fn message_json_identity() {
+            // Verify that "good" messages ser/de to JSON as an identity function.
+            let deserialized = $make_deserialized();
+            let json = serde_json::to_string(&deserialized).unwrap();
+            assert_eq!(
+                serde_json::from_str::<$struct_name>(&json).unwrap(),
+                deserialized
+            );
+        }
+
         #[test]
         fn message_type_tautology() {
-            // Verify that this message's MessageType enum variant is the same as its struct name.
-            assert_eq!($struct_name::message_type(), MessageType::$struct_name);
+            // Verify that this message's MessageType enum variant is the same as its struct name,
+            // or the explicitly specified variant.
+            assert_eq!($struct_name::message_type(), $message_type);
         }
 
         #[test]
@@ -92,7 +166,7 @@ macro_rules! impl_message_tests {
             // Verify that "good" messages and message payloads (un)frame as an identity function.
             let deserialized = $make_deserialized();
             let serialized = $make_serialized();
-            let message = Message::new(MessageType::$struct_name, serialized);
+            let message = Message::new($message_type, serialized);
             assert_eq!(frame(&deserialized).unwrap(), message);
             assert_eq!(unframe::<$struct_name>(&message).unwrap(), deserialized);
         }
@@ -104,6 +178,7 @@ macro_rules! impl_message_tests {
             let deserialized_frame = Message::new($struct_name::message_type(), $make_serialized());
 
             assert_eq!(serialize(&deserialized_frame).unwrap(), serialized_frame);
+            assert_eq!(deserialized_frame.encoded_len(), serialized_frame.len());
             assert_eq!(
                 deserialize::<Message>(&serialized_frame).unwrap(),
                 deserialized_frame
@@ -1,10 +1,10 @@
 pub mod macro_prelude {
-    pub use crate::codec::{ByteParser, Deserializable, Serializable};
+    pub use crate::codec::{io, ByteParser, Deserializable, Serializable};
     pub use crate::error::{Error, Result};
     pub use crate::impl_message;
+    pub use crate::prelude::*;
     pub use crate::types::{MessageType, STR0_255};
-    pub use std::convert::TryInto;
-    pub use std::io;
+    pub use core::convert::TryInto;
 }
 
 /// Implemention of the requirements for a SetupConnection message for each
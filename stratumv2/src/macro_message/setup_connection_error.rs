@@ -1,9 +1,9 @@
 pub mod macro_prelude {
+    pub use crate::codec::io;
     pub use crate::common::SetupConnectionErrorCode;
     pub use crate::error::{Error, Result};
     pub use crate::impl_message;
     pub use crate::types::{MessageType, STR0_255};
-    pub use std::io;
 }
 
 /// Implementation of the SetupConnectionError message for each sub protocol.
@@ -65,6 +65,26 @@ macro_rules! impl_setup_connection_error {
                 Ok(SetupConnectionError { flags, error_code })
             }
         }
+
+        impl From<SetupConnectionError> for crate::common::SetupConnectionError {
+            fn from(conn_error: SetupConnectionError) -> Self {
+                crate::common::SetupConnectionError {
+                    flags: conn_error.flags.bits(),
+                    error_code: conn_error.error_code,
+                }
+            }
+        }
+
+        impl core::convert::TryFrom<crate::common::SetupConnectionError> for SetupConnectionError {
+            type Error = Error;
+
+            fn try_from(conn_error: crate::common::SetupConnectionError) -> Result<Self> {
+                SetupConnectionError::new(
+                    $flags_type::from_bits(conn_error.flags).ok_or(Error::UnknownFlags())?,
+                    conn_error.error_code,
+                )
+            }
+        }
     };
 }
 
@@ -106,6 +126,23 @@ macro_rules! impl_setup_connection_error_tests {
             make_deserialized_setup_connection_error
         );
 
+        #[test]
+        fn common_setup_connection_error_conversion() {
+            use core::convert::TryInto;
+
+            // The common message has the same representation with raw flags.
+            let common = crate::common::SetupConnectionError::from(
+                make_deserialized_setup_connection_error(),
+            );
+            assert_eq!(
+                crate::codec::serialize(&common).unwrap(),
+                make_serialized_setup_connection_error()
+            );
+
+            let conn_error: SetupConnectionError = common.try_into().unwrap();
+            assert_eq!(conn_error, make_deserialized_setup_connection_error());
+        }
+
         #[test]
         fn empty_feature_flags_error() {
             assert!(matches!(
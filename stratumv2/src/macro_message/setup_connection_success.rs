@@ -1,8 +1,8 @@
 pub mod macro_prelude {
+    pub use crate::codec::io;
     pub use crate::error::{Error, Result};
     pub use crate::impl_message;
     pub use crate::types::MessageType;
-    pub use std::io;
 }
 
 #[doc(hidden)]
@@ -50,6 +50,26 @@ macro_rules! impl_setup_connection_success {
                 })
             }
         }
+
+        impl From<SetupConnectionSuccess> for crate::common::SetupConnectionSuccess {
+            fn from(conn_success: SetupConnectionSuccess) -> Self {
+                crate::common::SetupConnectionSuccess {
+                    used_version: conn_success.used_version,
+                    flags: conn_success.flags.bits(),
+                }
+            }
+        }
+
+        impl core::convert::TryFrom<crate::common::SetupConnectionSuccess> for SetupConnectionSuccess {
+            type Error = Error;
+
+            fn try_from(conn_success: crate::common::SetupConnectionSuccess) -> Result<Self> {
+                SetupConnectionSuccess::new(
+                    conn_success.used_version,
+                    $flags_type::from_bits(conn_success.flags).ok_or(Error::UnknownFlags())?,
+                )
+            }
+        }
     };
 }
 
@@ -83,5 +103,22 @@ macro_rules! impl_setup_connection_success_tests {
             make_serialized_setup_connection_success,
             make_deserialized_setup_connection_success
         );
+
+        #[test]
+        fn common_setup_connection_success_conversion() {
+            use core::convert::TryInto;
+
+            // The common message has the same representation with raw flags.
+            let common = crate::common::SetupConnectionSuccess::from(
+                make_deserialized_setup_connection_success(),
+            );
+            assert_eq!(
+                crate::codec::serialize(&common).unwrap(),
+                make_serialized_setup_connection_success()
+            );
+
+            let conn_success: SetupConnectionSuccess = common.try_into().unwrap();
+            assert_eq!(conn_success, make_deserialized_setup_connection_success());
+        }
     };
 }
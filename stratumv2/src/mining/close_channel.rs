@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, STR0_32};
+
+impl_message!(
+    /// CloseChannel is sent from the Client to the Server when it ends its
+    /// operation on a channel. The Server MUST stop sending messages for the
+    /// channel. A proxy MUST send this message on behalf of all opened channels
+    /// from a downstream connection when the downstream connection is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::CloseChannel;
+    ///
+    /// let close = CloseChannel::new(1, "shutting-down").unwrap();
+    /// assert_eq!(close.reason_code, "shutting-down".to_string());
+    /// ```
+    CloseChannel,
+
+    /// The channel to close.
+    channel_id u32,
+
+    /// The reason for closing the channel. The specification does not define
+    /// any reason codes, so this is a free-form string.
+    reason_code STR0_32
+);
+
+impl CloseChannel {
+    pub fn new<T: Into<String>>(channel_id: u32, reason_code: T) -> Result<CloseChannel> {
+        Ok(CloseChannel {
+            channel_id,
+            reason_code: STR0_32::new(reason_code)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_close_channel() -> CloseChannel {
+        CloseChannel::new(1u32, "reason").unwrap()
+    }
+
+    fn make_serialized_close_channel() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x06, 0x72, 0x65, 0x61, 0x73, 0x6f, 0x6e, // reason_code
+        ];
+    }
+
+    impl_message_tests!(
+        CloseChannel,
+        make_serialized_close_channel,
+        make_deserialized_close_channel
+    );
+
+    #[test]
+    fn reason_code_too_long() {
+        let reason: String = (0..33).map(|_| 'a').collect();
+        assert!(matches!(
+            CloseChannel::new(1u32, reason),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+}
@@ -14,6 +14,9 @@
 //!                     opened to a particular connection. The group is addressable
 //!                     through a common communication channel.
 
+mod close_channel;
+mod new_extended_mining_job;
+mod new_mining_job;
 mod open_extended_mining_channel;
 mod open_extended_mining_channel_error;
 mod open_extended_mining_channel_success;
@@ -21,11 +24,27 @@ mod open_mining_channel_error;
 mod open_standard_mining_channel;
 mod open_standard_mining_channel_error;
 mod open_standard_mining_channel_success;
+mod reconnect;
+mod set_custom_mining_job;
+mod set_custom_mining_job_error;
+mod set_custom_mining_job_success;
+mod set_extranonce_prefix;
+mod set_group_channel;
+mod set_new_prev_hash;
+mod set_target;
 mod setup_connection;
 mod setup_connection_error;
 mod setup_connection_success;
+mod submit_shares_error;
+mod submit_shares_extended;
+mod submit_shares_standard;
+mod submit_shares_success;
 mod update_channel;
+mod update_channel_error;
 
+pub use close_channel::CloseChannel;
+pub use new_extended_mining_job::NewExtendedMiningJob;
+pub use new_mining_job::NewMiningJob;
 pub use open_extended_mining_channel::OpenExtendedMiningChannel;
 pub use open_extended_mining_channel_error::OpenExtendedMiningChannelError;
 pub use open_extended_mining_channel_success::OpenExtendedMiningChannelSuccess;
@@ -33,7 +52,20 @@ pub use open_mining_channel_error::OpenMiningChannelErrorCode;
 pub use open_standard_mining_channel::OpenStandardMiningChannel;
 pub use open_standard_mining_channel_error::OpenStandardMiningChannelError;
 pub use open_standard_mining_channel_success::OpenStandardMiningChannelSuccess;
+pub use reconnect::Reconnect;
+pub use set_custom_mining_job::SetCustomMiningJob;
+pub use set_custom_mining_job_error::{SetCustomMiningJobError, SetCustomMiningJobErrorCode};
+pub use set_custom_mining_job_success::SetCustomMiningJobSuccess;
+pub use set_extranonce_prefix::SetExtranoncePrefix;
+pub use set_group_channel::SetGroupChannel;
+pub use set_new_prev_hash::SetNewPrevHash;
+pub use set_target::SetTarget;
 pub use setup_connection::{SetupConnection, SetupConnectionFlags};
 pub use setup_connection_error::SetupConnectionError;
 pub use setup_connection_success::{SetupConnectionSuccess, SetupConnectionSuccessFlags};
+pub use submit_shares_error::{SubmitSharesError, SubmitSharesErrorCode};
+pub use submit_shares_extended::SubmitSharesExtended;
+pub use submit_shares_standard::SubmitSharesStandard;
+pub use submit_shares_success::SubmitSharesSuccess;
 pub use update_channel::UpdateChannel;
+pub use update_channel_error::{UpdateChannelError, UpdateChannelErrorCode};
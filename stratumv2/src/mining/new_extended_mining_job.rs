@@ -0,0 +1,145 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_64K, SEQ0_255, U256};
+
+impl_message!(
+    /// NewExtendedMiningJob is sent from the Server to the Client to provide an
+    /// updated mining job on an extended channel. When sent on a group channel,
+    /// the job is broadcast to all standard channels belonging to the group.
+    ///
+    /// The Client builds the coinbase transaction from
+    /// `coinbase_tx_prefix + extranonce_prefix + extranonce + coinbase_tx_suffix`
+    /// and then computes the merkle root using the merkle_path.
+    ///
+    /// If min_ntime is empty, the job is a future job and MUST NOT be mined
+    /// until a [SetNewPrevHash](struct.SetNewPrevHash.html) message with a
+    /// matching job_id is received.
+    NewExtendedMiningJob,
+
+    /// The extended or group channel that the job is for.
+    channel_id u32,
+
+    /// The Server's identifier for the mining job. This identifier MUST be
+    /// provided to the Server when shares are submitted.
+    job_id u32,
+
+    /// The smallest nTime value available for hashing the job. An empty value
+    /// indicates a future job that will be activated by a SetNewPrevHash
+    /// message. If the value is set, the job is active immediately and uses
+    /// the prev_hash from the last received SetNewPrevHash message.
+    min_ntime Option<u32>,
+
+    /// A valid version field that reflects the current network consensus.
+    version u32,
+
+    /// If true, the general purpose bits of the version (as specified in
+    /// BIP320) can be freely manipulated by the Client. If false, the Client
+    /// MUST use the version as it is defined by this message.
+    version_rolling_allowed bool,
+
+    /// The merkle path hashes ordered from deepest.
+    merkle_path SEQ0_255<U256>,
+
+    /// The prefix part of the coinbase transaction.
+    coinbase_tx_prefix B0_64K,
+
+    /// The suffix part of the coinbase transaction.
+    coinbase_tx_suffix B0_64K
+);
+
+impl NewExtendedMiningJob {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<T: Into<Vec<U256>>, U: Into<Vec<u8>>>(
+        channel_id: u32,
+        job_id: u32,
+        min_ntime: Option<u32>,
+        version: u32,
+        version_rolling_allowed: bool,
+        merkle_path: T,
+        coinbase_tx_prefix: U,
+        coinbase_tx_suffix: U,
+    ) -> Result<NewExtendedMiningJob> {
+        Ok(NewExtendedMiningJob {
+            channel_id,
+            job_id,
+            min_ntime,
+            version,
+            version_rolling_allowed,
+            merkle_path: SEQ0_255::new(merkle_path)?,
+            coinbase_tx_prefix: B0_64K::new(coinbase_tx_prefix)?,
+            coinbase_tx_suffix: B0_64K::new(coinbase_tx_suffix)?,
+        })
+    }
+
+    /// Returns true if the job is a future job, meaning it can only be mined
+    /// once activated by a SetNewPrevHash message.
+    pub fn is_future_job(&self) -> bool {
+        self.min_ntime.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_new_extended_mining_job() -> NewExtendedMiningJob {
+        NewExtendedMiningJob::new(
+            1u32,
+            2u32,
+            None,
+            3u32,
+            true,
+            vec![U256([4u8; 32]), U256([5u8; 32])],
+            [6u8; 2],
+            [7u8; 2],
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_new_extended_mining_job() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // job_id
+            0x00, // min_ntime
+            0x03, 0x00, 0x00, 0x00, // version
+            0x01, // version_rolling_allowed
+            0x02, // merkle_path length
+            0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04,
+            0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04,
+            0x04, 0x04, 0x04, 0x04, // merkle_path[0]
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, // merkle_path[1]
+            0x02, 0x00, 0x06, 0x06, // coinbase_tx_prefix
+            0x02, 0x00, 0x07, 0x07, // coinbase_tx_suffix
+        ];
+    }
+
+    impl_message_tests!(
+        NewExtendedMiningJob,
+        make_serialized_new_extended_mining_job,
+        make_deserialized_new_extended_mining_job
+    );
+
+    #[test]
+    fn future_job() {
+        assert!(make_deserialized_new_extended_mining_job().is_future_job());
+    }
+
+    #[test]
+    fn merkle_path_too_long() {
+        assert!(NewExtendedMiningJob::new(
+            1u32,
+            2u32,
+            None,
+            3u32,
+            true,
+            vec![U256([4u8; 32]); 256],
+            [6u8; 2],
+            [7u8; 2],
+        )
+        .is_err());
+    }
+}
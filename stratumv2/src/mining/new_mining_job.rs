@@ -0,0 +1,110 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_32};
+
+impl_message!(
+    /// NewMiningJob is sent from the Server to the Client to provide an updated
+    /// mining job on a standard channel. The merkle root is precomputed by the
+    /// Server, so the Client only needs to roll the header fields.
+    ///
+    /// If min_ntime is empty, the job is a future job and MUST NOT be mined
+    /// until a [SetNewPrevHash](struct.SetNewPrevHash.html) message with a
+    /// matching job_id is received.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::NewMiningJob;
+    ///
+    /// let future_job = NewMiningJob::new(1, 2, None, 3, [4u8; 32]).unwrap();
+    /// assert!(future_job.is_future_job());
+    ///
+    /// let active_job = NewMiningJob::new(1, 2, Some(1618994045), 3, [4u8; 32]).unwrap();
+    /// assert!(!active_job.is_future_job());
+    /// ```
+    NewMiningJob,
+
+    /// The standard channel that the job is for.
+    channel_id u32,
+
+    /// The Server's identifier for the mining job. This identifier MUST be
+    /// provided to the Server when shares are submitted.
+    job_id u32,
+
+    /// The smallest nTime value available for hashing the job. An empty value
+    /// indicates a future job that will be activated by a SetNewPrevHash
+    /// message. If the value is set, the job is active immediately and uses
+    /// the prev_hash from the last received SetNewPrevHash message.
+    min_ntime Option<u32>,
+
+    /// A valid version field that reflects the current network consensus. The
+    /// general purpose bits (as specified in BIP320) can be freely manipulated
+    /// by the Client.
+    version u32,
+
+    /// The merkle root field as used in the bitcoin block header.
+    merkle_root B0_32
+);
+
+impl NewMiningJob {
+    pub fn new<T: Into<Vec<u8>>>(
+        channel_id: u32,
+        job_id: u32,
+        min_ntime: Option<u32>,
+        version: u32,
+        merkle_root: T,
+    ) -> Result<NewMiningJob> {
+        Ok(NewMiningJob {
+            channel_id,
+            job_id,
+            min_ntime,
+            version,
+            merkle_root: B0_32::new(merkle_root)?,
+        })
+    }
+
+    /// Returns true if the job is a future job, meaning it can only be mined
+    /// once activated by a SetNewPrevHash message.
+    pub fn is_future_job(&self) -> bool {
+        self.min_ntime.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_new_mining_job() -> NewMiningJob {
+        NewMiningJob::new(1u32, 2u32, Some(3u32), 4u32, [5u8; 32]).unwrap()
+    }
+
+    fn make_serialized_new_mining_job() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // job_id
+            0x01, 0x03, 0x00, 0x00, 0x00, // min_ntime
+            0x04, 0x00, 0x00, 0x00, // version
+            0x20, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, 0x05, // merkle_root
+        ];
+    }
+
+    impl_message_tests!(
+        NewMiningJob,
+        make_serialized_new_mining_job,
+        make_deserialized_new_mining_job
+    );
+
+    #[test]
+    fn future_job_serde() {
+        let job = NewMiningJob::new(1u32, 2u32, None, 4u32, [5u8; 32]).unwrap();
+        assert!(job.is_future_job());
+
+        let serialized = serialize(&job).unwrap();
+        assert_eq!(serialized[8], 0x00);
+        assert_eq!(deserialize::<NewMiningJob>(&serialized).unwrap(), job);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::impl_message;
+use crate::prelude::*;
 use crate::types::{MessageType, STR0_255, U256};
 
 impl_message!(
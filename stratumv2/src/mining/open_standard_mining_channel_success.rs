@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::impl_message;
+use crate::prelude::*;
 use crate::types::{MessageType, B0_32, U256};
 
 impl_message!(
@@ -0,0 +1,65 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, STR0_255};
+
+impl_message!(
+    /// Reconnect is sent from the Server to the Client to redirect the
+    /// connection to a different host. This allows the Server to be drained
+    /// for maintenance without the Client losing hashrate. The Client MUST
+    /// close the current connection and open a new connection to the new
+    /// target.
+    ///
+    /// A [network::Endpoint](../network/struct.Endpoint.html) can be used to
+    /// resolve the new connection target from the current one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::Reconnect;
+    ///
+    /// let reconnect = Reconnect::new("pool2.example.com", 3336).unwrap();
+    /// assert_eq!(reconnect.new_host, "pool2.example.com".to_string());
+    /// ```
+    Reconnect,
+
+    /// The new host to connect to. When empty, the Client attempts to
+    /// reconnect to its present host.
+    new_host STR0_255,
+
+    /// The new port to connect to. When 0, the Client attempts to reconnect to
+    /// its present port.
+    new_port u16
+);
+
+impl Reconnect {
+    pub fn new<T: Into<String>>(new_host: T, new_port: u16) -> Result<Reconnect> {
+        Ok(Reconnect {
+            new_host: STR0_255::new(new_host)?,
+            new_port,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_reconnect() -> Reconnect {
+        Reconnect::new("0.0.0.0", 8545).unwrap()
+    }
+
+    fn make_serialized_reconnect() -> Vec<u8> {
+        return vec![
+            0x07, 0x30, 0x2e, 0x30, 0x2e, 0x30, 0x2e, 0x30, // new_host
+            0x61, 0x21, // new_port
+        ];
+    }
+
+    impl_message_tests!(
+        Reconnect,
+        make_serialized_reconnect,
+        make_deserialized_reconnect
+    );
+}
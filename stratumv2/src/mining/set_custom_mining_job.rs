@@ -0,0 +1,166 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_255, B0_64K, SEQ0_255, U256};
+
+impl_message!(
+    /// SetCustomMiningJob is sent from the Client to the Server to set a custom
+    /// mining job on an extended channel. The Client MUST have set
+    /// [REQUIRES_WORK_SELECTION](struct.SetupConnectionFlags.html) in the
+    /// SetupConnection message of the connection and the job MUST have been
+    /// negotiated with a Job Negotiator, which provides the mining_job_token.
+    SetCustomMiningJob,
+
+    /// The extended channel that the custom job is for.
+    channel_id u32,
+
+    /// A Client-specified identifier for pairing responses.
+    request_id u32,
+
+    /// The token provided by the pool which uniquely identifies the job that
+    /// was negotiated with the Job Negotiator.
+    mining_job_token B0_255,
+
+    /// A valid version field that reflects the current network consensus. The
+    /// general purpose bits (as specified in BIP320) can be freely manipulated
+    /// by the Client.
+    version u32,
+
+    /// The previous block hash to be used in the block header.
+    prev_hash U256,
+
+    /// The smallest nTime value available for hashing.
+    min_ntime u32,
+
+    /// The nBits field of the block header.
+    nbits u32,
+
+    /// The coinbase transaction nVersion field.
+    coinbase_tx_version u32,
+
+    /// Up to 8 bytes (not including the length byte) which are to be placed at
+    /// the beginning of the coinbase field in the coinbase transaction.
+    coinbase_prefix B0_255,
+
+    /// The coinbase transaction input's nSequence field.
+    coinbase_tx_input_nsequence u32,
+
+    /// The value, in satoshis, available for spending in coinbase outputs added
+    /// by the Client. Includes both transaction fees and block subsidy.
+    coinbase_tx_value_remaining u64,
+
+    /// All the outputs that will be included in the coinbase transaction.
+    coinbase_tx_outputs B0_64K,
+
+    /// The locktime field in the coinbase transaction.
+    coinbase_tx_locktime u32,
+
+    /// The merkle path hashes ordered from deepest.
+    merkle_path SEQ0_255<U256>,
+
+    /// The size of the extranonce space used by the custom job.
+    extranonce_size u16
+);
+
+impl SetCustomMiningJob {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<
+        T: Into<Vec<u8>>,
+        U: Into<U256>,
+        V: Into<Vec<u8>>,
+        W: Into<Vec<u8>>,
+        X: Into<Vec<U256>>,
+    >(
+        channel_id: u32,
+        request_id: u32,
+        mining_job_token: T,
+        version: u32,
+        prev_hash: U,
+        min_ntime: u32,
+        nbits: u32,
+        coinbase_tx_version: u32,
+        coinbase_prefix: V,
+        coinbase_tx_input_nsequence: u32,
+        coinbase_tx_value_remaining: u64,
+        coinbase_tx_outputs: W,
+        coinbase_tx_locktime: u32,
+        merkle_path: X,
+        extranonce_size: u16,
+    ) -> Result<SetCustomMiningJob> {
+        Ok(SetCustomMiningJob {
+            channel_id,
+            request_id,
+            mining_job_token: B0_255::new(mining_job_token)?,
+            version,
+            prev_hash: prev_hash.into(),
+            min_ntime,
+            nbits,
+            coinbase_tx_version,
+            coinbase_prefix: B0_255::new(coinbase_prefix)?,
+            coinbase_tx_input_nsequence,
+            coinbase_tx_value_remaining,
+            coinbase_tx_outputs: B0_64K::new(coinbase_tx_outputs)?,
+            coinbase_tx_locktime,
+            merkle_path: SEQ0_255::new(merkle_path)?,
+            extranonce_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_custom_mining_job() -> SetCustomMiningJob {
+        SetCustomMiningJob::new(
+            1u32,
+            2u32,
+            [3u8; 2],
+            4u32,
+            [5u8; 32],
+            6u32,
+            7u32,
+            8u32,
+            [9u8; 2],
+            10u32,
+            11u64,
+            [12u8; 2],
+            13u32,
+            vec![U256([14u8; 32])],
+            15u16,
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_set_custom_mining_job() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // request_id
+            0x02, 0x03, 0x03, // mining_job_token
+            0x04, 0x00, 0x00, 0x00, // version
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, // prev_hash
+            0x06, 0x00, 0x00, 0x00, // min_ntime
+            0x07, 0x00, 0x00, 0x00, // nbits
+            0x08, 0x00, 0x00, 0x00, // coinbase_tx_version
+            0x02, 0x09, 0x09, // coinbase_prefix
+            0x0a, 0x00, 0x00, 0x00, // coinbase_tx_input_nsequence
+            0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // coinbase_tx_value_remaining
+            0x02, 0x00, 0x0c, 0x0c, // coinbase_tx_outputs
+            0x0d, 0x00, 0x00, 0x00, // coinbase_tx_locktime
+            0x01, // merkle_path length
+            0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e,
+            0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e, 0x0e,
+            0x0e, 0x0e, 0x0e, 0x0e, // merkle_path[0]
+            0x0f, 0x00, // extranonce_size
+        ];
+    }
+
+    impl_message_tests!(
+        SetCustomMiningJob,
+        make_serialized_set_custom_mining_job,
+        make_deserialized_set_custom_mining_job
+    );
+}
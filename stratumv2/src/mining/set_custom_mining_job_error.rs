@@ -0,0 +1,132 @@
+use crate::error::Result;
+use crate::impl_error_code_enum;
+use crate::impl_message;
+use crate::types::MessageType;
+
+/// Contains the error codes for the [SetCustomMiningJobError](struct.SetCustomMiningJobError.html)
+/// message. Each error code has a default STR0_255 message.
+///
+/// The specification defines `invalid-job-param-value-{}` where `{}` is the
+/// name of the [SetCustomMiningJob](struct.SetCustomMiningJob.html) field with
+/// an invalid value. Each of those fields is represented by its own variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetCustomMiningJobErrorCode {
+    /// The channel_id is unknown to the Server or is not an extended channel.
+    InvalidChannelId,
+
+    /// The mining_job_token was not allocated by the Server or has expired.
+    InvalidMiningJobToken,
+
+    InvalidJobParamValueVersion,
+    InvalidJobParamValuePrevHash,
+    InvalidJobParamValueMinNtime,
+    InvalidJobParamValueNbits,
+    InvalidJobParamValueCoinbaseTxVersion,
+    InvalidJobParamValueCoinbasePrefix,
+    InvalidJobParamValueCoinbaseTxInputNSequence,
+    InvalidJobParamValueCoinbaseTxValueRemaining,
+    InvalidJobParamValueCoinbaseTxOutputs,
+    InvalidJobParamValueCoinbaseTxLocktime,
+    InvalidJobParamValueMerklePath,
+    InvalidJobParamValueExtranonceSize,
+}
+
+impl_error_code_enum!(
+    SetCustomMiningJobErrorCode,
+    SetCustomMiningJobErrorCode::InvalidChannelId => "invalid-channel-id",
+    SetCustomMiningJobErrorCode::InvalidMiningJobToken => "invalid-mining-job-token",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueVersion => "invalid-job-param-value-version",
+    SetCustomMiningJobErrorCode::InvalidJobParamValuePrevHash => "invalid-job-param-value-prev_hash",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueMinNtime => "invalid-job-param-value-min_ntime",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueNbits => "invalid-job-param-value-nbits",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxVersion => "invalid-job-param-value-coinbase_tx_version",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbasePrefix => "invalid-job-param-value-coinbase_prefix",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxInputNSequence => "invalid-job-param-value-coinbase_tx_input_nSequence",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxValueRemaining => "invalid-job-param-value-coinbase_tx_value_remaining",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs => "invalid-job-param-value-coinbase_tx_outputs",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxLocktime => "invalid-job-param-value-coinbase_tx_locktime",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueMerklePath => "invalid-job-param-value-merkle_path",
+    SetCustomMiningJobErrorCode::InvalidJobParamValueExtranonceSize => "invalid-job-param-value-extranonce_size"
+);
+
+impl_message!(
+    /// SetCustomMiningJobError is sent from the Server to the Client in
+    /// response to a rejected [SetCustomMiningJob](struct.SetCustomMiningJob.html)
+    /// message.
+    SetCustomMiningJobError,
+
+    /// The extended channel that the custom job was set on.
+    channel_id u32,
+
+    /// The request_id received in the SetCustomMiningJob message.
+    request_id u32,
+
+    /// Pre-determined human readable error codes for the SetCustomMiningJob message.
+    error_code SetCustomMiningJobErrorCode
+);
+
+impl SetCustomMiningJobError {
+    pub fn new(
+        channel_id: u32,
+        request_id: u32,
+        error_code: SetCustomMiningJobErrorCode,
+    ) -> Result<SetCustomMiningJobError> {
+        Ok(SetCustomMiningJobError {
+            channel_id,
+            request_id,
+            error_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_custom_mining_job_error() -> SetCustomMiningJobError {
+        SetCustomMiningJobError::new(
+            1u32,
+            2u32,
+            SetCustomMiningJobErrorCode::InvalidJobParamValueNbits,
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_set_custom_mining_job_error() -> Vec<u8> {
+        let mut serialized = vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // request_id
+        ];
+        serialized.push(29); // error_code length
+        serialized.extend("invalid-job-param-value-nbits".as_bytes()); // error_code
+
+        serialized
+    }
+
+    impl_message_tests!(
+        SetCustomMiningJobError,
+        make_serialized_set_custom_mining_job_error,
+        make_deserialized_set_custom_mining_job_error
+    );
+
+    #[test]
+    fn error_code_from_str() {
+        assert_eq!(
+            "invalid-mining-job-token"
+                .parse::<SetCustomMiningJobErrorCode>()
+                .unwrap(),
+            SetCustomMiningJobErrorCode::InvalidMiningJobToken
+        );
+        assert_eq!(
+            "invalid-job-param-value-coinbase_tx_input_nSequence"
+                .parse::<SetCustomMiningJobErrorCode>()
+                .unwrap(),
+            SetCustomMiningJobErrorCode::InvalidJobParamValueCoinbaseTxInputNSequence
+        );
+        assert!(matches!(
+            "invalid-job-param-value-unknown".parse::<SetCustomMiningJobErrorCode>(),
+            Err(Error::UnknownErrorCode { .. })
+        ));
+    }
+}
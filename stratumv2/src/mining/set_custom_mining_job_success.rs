@@ -0,0 +1,54 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// SetCustomMiningJobSuccess is sent from the Server to the Client in
+    /// response to an accepted [SetCustomMiningJob](struct.SetCustomMiningJob.html)
+    /// message.
+    SetCustomMiningJobSuccess,
+
+    /// The extended channel that the custom job was set on.
+    channel_id u32,
+
+    /// The request_id received in the SetCustomMiningJob message.
+    request_id u32,
+
+    /// The Server's identifier for the mining job. This identifier MUST be
+    /// provided to the Server when shares are submitted.
+    job_id u32
+);
+
+impl SetCustomMiningJobSuccess {
+    pub fn new(channel_id: u32, request_id: u32, job_id: u32) -> Result<SetCustomMiningJobSuccess> {
+        Ok(SetCustomMiningJobSuccess {
+            channel_id,
+            request_id,
+            job_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_custom_mining_job_success() -> SetCustomMiningJobSuccess {
+        SetCustomMiningJobSuccess::new(1u32, 2u32, 3u32).unwrap()
+    }
+
+    fn make_serialized_set_custom_mining_job_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // request_id
+            0x03, 0x00, 0x00, 0x00, // job_id
+        ];
+    }
+
+    impl_message_tests!(
+        SetCustomMiningJobSuccess,
+        make_serialized_set_custom_mining_job_success,
+        make_deserialized_set_custom_mining_job_success
+    );
+}
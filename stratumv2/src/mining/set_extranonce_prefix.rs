@@ -0,0 +1,65 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_32};
+
+impl_message!(
+    /// SetExtranoncePrefix is sent from the Server to the Client to change the
+    /// extranonce prefix of a channel. The new prefix applies to all jobs sent
+    /// after this message on the channel, including jobs set by a
+    /// [SetCustomMiningJob](struct.SetCustomMiningJob.html) message.
+    ///
+    /// This message is only applicable to explicitly opened channels and not
+    /// to group channels.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::SetExtranoncePrefix;
+    ///
+    /// let prefix = SetExtranoncePrefix::new(1, [0u8; 4]);
+    /// assert!(prefix.is_ok());
+    /// ```
+    SetExtranoncePrefix,
+
+    /// The channel that the extranonce prefix applies to.
+    channel_id u32,
+
+    /// The bytes used as the implicit first part of the extranonce.
+    extranonce_prefix B0_32
+);
+
+impl SetExtranoncePrefix {
+    pub fn new<T: Into<Vec<u8>>>(
+        channel_id: u32,
+        extranonce_prefix: T,
+    ) -> Result<SetExtranoncePrefix> {
+        Ok(SetExtranoncePrefix {
+            channel_id,
+            extranonce_prefix: B0_32::new(extranonce_prefix)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_extranonce_prefix() -> SetExtranoncePrefix {
+        SetExtranoncePrefix::new(1u32, [2u8; 4]).unwrap()
+    }
+
+    fn make_serialized_set_extranonce_prefix() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x04, 0x02, 0x02, 0x02, 0x02, // extranonce_prefix
+        ];
+    }
+
+    impl_message_tests!(
+        SetExtranoncePrefix,
+        make_serialized_set_extranonce_prefix,
+        make_deserialized_set_extranonce_prefix
+    );
+}
@@ -0,0 +1,68 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, SEQ0_64K};
+
+impl_message!(
+    /// SetGroupChannel is sent from the Server to the Client to associate a set
+    /// of standard channels with a group channel. A group channel is used for
+    /// efficient job distribution, allowing a single NewExtendedMiningJob to be
+    /// sent to all the standard channels in the group.
+    ///
+    /// A standard channel can only be a member of a single group. It becomes a
+    /// member of a group when the channel is opened, or when it is reassigned
+    /// to another group by this message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::SetGroupChannel;
+    ///
+    /// let group = SetGroupChannel::new(1, vec![2, 3, 4]).unwrap();
+    /// assert_eq!(group.channel_ids, vec![2, 3, 4]);
+    /// ```
+    SetGroupChannel,
+
+    /// The identifier of the group channel.
+    group_channel_id u32,
+
+    /// The standard channels that are members of the group.
+    channel_ids SEQ0_64K<u32>
+);
+
+impl SetGroupChannel {
+    pub fn new<T: Into<Vec<u32>>>(
+        group_channel_id: u32,
+        channel_ids: T,
+    ) -> Result<SetGroupChannel> {
+        Ok(SetGroupChannel {
+            group_channel_id,
+            channel_ids: SEQ0_64K::new(channel_ids)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_group_channel() -> SetGroupChannel {
+        SetGroupChannel::new(1u32, vec![2u32, 3u32]).unwrap()
+    }
+
+    fn make_serialized_set_group_channel() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // group_channel_id
+            0x02, 0x00, // channel_ids length
+            0x02, 0x00, 0x00, 0x00, // channel_ids[0]
+            0x03, 0x00, 0x00, 0x00, // channel_ids[1]
+        ];
+    }
+
+    impl_message_tests!(
+        SetGroupChannel,
+        make_serialized_set_group_channel,
+        make_deserialized_set_group_channel
+    );
+}
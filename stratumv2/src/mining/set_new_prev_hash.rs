@@ -0,0 +1,90 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::{MessageType, U256};
+
+impl_message!(
+    /// SetNewPrevHash is sent from the Server to the Client to notify that a
+    /// new block has been found on the network. The message activates the
+    /// future job identified by job_id and all other jobs on the channel become
+    /// invalid. The Client MUST start mining on the activated job immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::SetNewPrevHash;
+    ///
+    /// let prev_hash = SetNewPrevHash::new(1, 2, [3u8; 32], 1618994045, 0x1d00ffff);
+    /// assert!(prev_hash.is_ok());
+    /// ```
+    SetNewPrevHash,
+
+    /// The group channel or channel that the prev_hash is valid for.
+    channel_id u32,
+
+    /// The identifier of the future job to be mined with this prev_hash.
+    job_id u32,
+
+    /// The previous block hash to be used in the block header.
+    prev_hash U256,
+
+    /// The smallest nTime value available for hashing.
+    min_ntime u32,
+
+    /// The nBits field of the block header.
+    nbits u32
+);
+
+impl SetNewPrevHash {
+    pub fn new<T: Into<U256>>(
+        channel_id: u32,
+        job_id: u32,
+        prev_hash: T,
+        min_ntime: u32,
+        nbits: u32,
+    ) -> Result<SetNewPrevHash> {
+        Ok(SetNewPrevHash {
+            channel_id,
+            job_id,
+            prev_hash: prev_hash.into(),
+            min_ntime,
+            nbits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_new_prev_hash() -> SetNewPrevHash {
+        SetNewPrevHash::new(1u32, 2u32, [3u8; 32], 4u32, 5u32).unwrap()
+    }
+
+    fn make_serialized_set_new_prev_hash() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // job_id
+            0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03,
+            0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03,
+            0x03, 0x03, 0x03, 0x03, // prev_hash
+            0x04, 0x00, 0x00, 0x00, // min_ntime
+            0x05, 0x00, 0x00, 0x00, // nbits
+        ];
+    }
+
+    impl_message_tests!(
+        SetNewPrevHash,
+        make_serialized_set_new_prev_hash,
+        make_deserialized_set_new_prev_hash
+    );
+
+    #[test]
+    fn frame_channel_bit() {
+        // SetNewPrevHash is addressed to a channel, so the MSB of the extension
+        // type MUST be set.
+        let network_message = frame(&make_deserialized_set_new_prev_hash()).unwrap();
+        let serialized = serialize(&network_message).unwrap();
+        assert_eq!(serialized[0..3], [0x00, 0x80, 0x20]);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::{MessageType, U256};
+
+impl_message!(
+    /// SetTarget is sent from the Server to the Client to control the rate of
+    /// share submissions on a channel. The new maximum_target is valid
+    /// immediately for all jobs on the channel, including jobs that were sent
+    /// before this message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::SetTarget;
+    ///
+    /// let target = SetTarget::new(1, [0xffu8; 32]);
+    /// assert!(target.is_ok());
+    /// ```
+    SetTarget,
+
+    /// The channel that the target applies to.
+    channel_id u32,
+
+    /// The maximum value of a produced hash that will be accepted by the Server
+    /// as a share.
+    maximum_target U256
+);
+
+impl SetTarget {
+    pub fn new<T: Into<U256>>(channel_id: u32, maximum_target: T) -> Result<SetTarget> {
+        Ok(SetTarget {
+            channel_id,
+            maximum_target: maximum_target.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_target() -> SetTarget {
+        SetTarget::new(1u32, [2u8; 32]).unwrap()
+    }
+
+    fn make_serialized_set_target() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, // maximum_target
+        ];
+    }
+
+    impl_message_tests!(
+        SetTarget,
+        make_serialized_set_target,
+        make_deserialized_set_target
+    );
+
+    #[test]
+    fn frame_channel_bit() {
+        // SetTarget is addressed to a channel, so the MSB of the extension type
+        // MUST be set.
+        let network_message = frame(&make_deserialized_set_target()).unwrap();
+        let serialized = serialize(&network_message).unwrap();
+        assert_eq!(serialized[0..3], [0x00, 0x80, 0x21]);
+    }
+}
@@ -18,7 +18,12 @@ bitflags!(
     }
 );
 
-impl_bitflags_serde!(SetupConnectionFlags);
+impl_bitflags_serde!(
+    SetupConnectionFlags,
+    REQUIRES_STANDARD_JOBS,
+    REQUIRES_WORK_SELECTION,
+    REQUIRES_VERSION_ROLLING
+);
 impl_setup_connection!(SetupConnectionFlags);
 
 #[cfg(test)]
@@ -64,5 +69,24 @@ mod tests {
         ));
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn flags_json() {
+        let flags = SetupConnectionFlags::REQUIRES_STANDARD_JOBS
+            | SetupConnectionFlags::REQUIRES_VERSION_ROLLING;
+        let json = r#"["REQUIRES_STANDARD_JOBS","REQUIRES_VERSION_ROLLING"]"#;
+
+        assert_eq!(serde_json::to_string(&flags).unwrap(), json);
+        assert_eq!(
+            serde_json::from_str::<SetupConnectionFlags>(json).unwrap(),
+            flags
+        );
+        assert_eq!(
+            serde_json::from_str::<SetupConnectionFlags>("[]").unwrap(),
+            SetupConnectionFlags::empty()
+        );
+        assert!(serde_json::from_str::<SetupConnectionFlags>(r#"["UNKNOWN"]"#).is_err());
+    }
+
     impl_setup_connection_tests!(SetupConnectionFlags);
 }
@@ -10,7 +10,11 @@ bitflags!(
     }
 );
 
-impl_bitflags_serde!(SetupConnectionSuccessFlags);
+impl_bitflags_serde!(
+    SetupConnectionSuccessFlags,
+    REQUIRES_FIXED_VERSION,
+    REQUIRES_EXTENDED_CHANNELS
+);
 impl_setup_connection_success!(SetupConnectionSuccessFlags);
 
 #[cfg(test)]
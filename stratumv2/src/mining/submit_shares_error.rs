@@ -0,0 +1,107 @@
+use crate::error::Result;
+use crate::impl_error_code_enum;
+use crate::impl_message;
+use crate::types::MessageType;
+
+/// Contains the error codes for the [SubmitSharesError](struct.SubmitSharesError.html)
+/// message. Each error code has a default STR0_255 message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmitSharesErrorCode {
+    /// The channel_id of the submission is unknown to the Server.
+    InvalidChannelId,
+
+    /// The share was submitted for a job that is no longer valid.
+    StaleShare,
+
+    /// The share does not meet the target of the channel.
+    DifficultyTooLow,
+
+    /// The job_id of the submission is unknown to the Server.
+    InvalidJobId,
+}
+
+impl_error_code_enum!(
+    SubmitSharesErrorCode,
+    SubmitSharesErrorCode::InvalidChannelId => "invalid-channel-id",
+    SubmitSharesErrorCode::StaleShare => "stale-share",
+    SubmitSharesErrorCode::DifficultyTooLow => "difficulty-too-low",
+    SubmitSharesErrorCode::InvalidJobId => "invalid-job-id"
+);
+
+impl_message!(
+    /// SubmitSharesError is sent from the Server to the Client when a submitted
+    /// share is rejected.
+    SubmitSharesError,
+
+    /// The channel that the share was submitted on.
+    channel_id u32,
+
+    /// The sequence number of the submission that this error is returned for.
+    sequence_number u32,
+
+    /// Pre-determined human readable error codes for the SubmitShares messages.
+    error_code SubmitSharesErrorCode
+);
+
+impl SubmitSharesError {
+    pub fn new(
+        channel_id: u32,
+        sequence_number: u32,
+        error_code: SubmitSharesErrorCode,
+    ) -> Result<SubmitSharesError> {
+        Ok(SubmitSharesError {
+            channel_id,
+            sequence_number,
+            error_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_submit_shares_error() -> SubmitSharesError {
+        SubmitSharesError::new(1u32, 2u32, SubmitSharesErrorCode::StaleShare).unwrap()
+    }
+
+    fn make_serialized_submit_shares_error() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // sequence_number
+            0x0b, 0x73, 0x74, 0x61, 0x6c, 0x65, 0x2d, 0x73, 0x68, 0x61, 0x72,
+            0x65, // error_code
+        ];
+    }
+
+    impl_message_tests!(
+        SubmitSharesError,
+        make_serialized_submit_shares_error,
+        make_deserialized_submit_shares_error
+    );
+
+    #[test]
+    fn error_code_from_str() {
+        assert_eq!(
+            "invalid-channel-id"
+                .parse::<SubmitSharesErrorCode>()
+                .unwrap(),
+            SubmitSharesErrorCode::InvalidChannelId
+        );
+        assert_eq!(
+            "difficulty-too-low"
+                .parse::<SubmitSharesErrorCode>()
+                .unwrap(),
+            SubmitSharesErrorCode::DifficultyTooLow
+        );
+        assert_eq!(
+            "invalid-job-id".parse::<SubmitSharesErrorCode>().unwrap(),
+            SubmitSharesErrorCode::InvalidJobId
+        );
+        assert!(matches!(
+            "unknown-error".parse::<SubmitSharesErrorCode>(),
+            Err(Error::UnknownErrorCode { .. })
+        ));
+    }
+}
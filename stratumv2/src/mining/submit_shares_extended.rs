@@ -0,0 +1,101 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_32};
+
+impl_message!(
+    /// SubmitSharesExtended is sent from the Client to the Server on an extended
+    /// channel to submit the result of its hashing work. It contains the same
+    /// fields as [SubmitSharesStandard](struct.SubmitSharesStandard.html) with
+    /// the addition of the extranonce used to form the coinbase transaction.
+    SubmitSharesExtended,
+
+    /// The channel that the share is submitted on.
+    channel_id u32,
+
+    /// A unique sequential identifier of the submission within the channel.
+    sequence_number u32,
+
+    /// The identifier of the job as provided by a
+    /// [NewMiningJob](struct.NewMiningJob.html) or
+    /// [NewExtendedMiningJob](struct.NewExtendedMiningJob.html) message.
+    job_id u32,
+
+    /// The nonce leading to the hash being submitted.
+    nonce u32,
+
+    /// The nTime field in the block header. This MUST be greater than or equal
+    /// to the header_timestamp field in the latest SetNewPrevHash message and
+    /// lower than or equal to that value plus the number of seconds since the
+    /// receipt of that message.
+    ntime u32,
+
+    /// The full nVersion field of the block header.
+    version u32,
+
+    /// The extranonce bytes which need to be added to the coinbase to form a
+    /// fully valid submission:
+    /// `coinbase_tx_prefix + extranonce_prefix + extranonce + coinbase_tx_suffix`.
+    /// The size of the extranonce MUST be equal to the extranonce_size
+    /// negotiated when opening the channel.
+    extranonce B0_32
+);
+
+impl SubmitSharesExtended {
+    pub fn new<T: Into<Vec<u8>>>(
+        channel_id: u32,
+        sequence_number: u32,
+        job_id: u32,
+        nonce: u32,
+        ntime: u32,
+        version: u32,
+        extranonce: T,
+    ) -> Result<SubmitSharesExtended> {
+        Ok(SubmitSharesExtended {
+            channel_id,
+            sequence_number,
+            job_id,
+            nonce,
+            ntime,
+            version,
+            extranonce: B0_32::new(extranonce)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_submit_shares_extended() -> SubmitSharesExtended {
+        SubmitSharesExtended::new(1u32, 2u32, 3u32, 4u32, 5u32, 6u32, [7u8; 4]).unwrap()
+    }
+
+    fn make_serialized_submit_shares_extended() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // sequence_number
+            0x03, 0x00, 0x00, 0x00, // job_id
+            0x04, 0x00, 0x00, 0x00, // nonce
+            0x05, 0x00, 0x00, 0x00, // ntime
+            0x06, 0x00, 0x00, 0x00, // version
+            0x04, 0x07, 0x07, 0x07, 0x07, // extranonce
+        ];
+    }
+
+    impl_message_tests!(
+        SubmitSharesExtended,
+        make_serialized_submit_shares_extended,
+        make_deserialized_submit_shares_extended
+    );
+
+    #[test]
+    fn extranonce_too_large() {
+        assert!(matches!(
+            SubmitSharesExtended::new(1u32, 2u32, 3u32, 4u32, 5u32, 6u32, [7u8; 33]),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// SubmitSharesStandard is sent from the Client to the Server on a standard
+    /// channel to submit the result of its hashing work.
+    SubmitSharesStandard,
+
+    /// The channel that the share is submitted on.
+    channel_id u32,
+
+    /// A unique sequential identifier of the submission within the channel.
+    sequence_number u32,
+
+    /// The identifier of the job as provided by a
+    /// [NewMiningJob](struct.NewMiningJob.html) or
+    /// [NewExtendedMiningJob](struct.NewExtendedMiningJob.html) message.
+    job_id u32,
+
+    /// The nonce leading to the hash being submitted.
+    nonce u32,
+
+    /// The nTime field in the block header. This MUST be greater than or equal
+    /// to the header_timestamp field in the latest SetNewPrevHash message and
+    /// lower than or equal to that value plus the number of seconds since the
+    /// receipt of that message.
+    ntime u32,
+
+    /// The full nVersion field of the block header.
+    version u32
+);
+
+impl SubmitSharesStandard {
+    pub fn new(
+        channel_id: u32,
+        sequence_number: u32,
+        job_id: u32,
+        nonce: u32,
+        ntime: u32,
+        version: u32,
+    ) -> Result<SubmitSharesStandard> {
+        Ok(SubmitSharesStandard {
+            channel_id,
+            sequence_number,
+            job_id,
+            nonce,
+            ntime,
+            version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_submit_shares_standard() -> SubmitSharesStandard {
+        SubmitSharesStandard::new(1u32, 2u32, 3u32, 4u32, 5u32, 6u32).unwrap()
+    }
+
+    fn make_serialized_submit_shares_standard() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // sequence_number
+            0x03, 0x00, 0x00, 0x00, // job_id
+            0x04, 0x00, 0x00, 0x00, // nonce
+            0x05, 0x00, 0x00, 0x00, // ntime
+            0x06, 0x00, 0x00, 0x00, // version
+        ];
+    }
+
+    impl_message_tests!(
+        SubmitSharesStandard,
+        make_serialized_submit_shares_standard,
+        make_deserialized_submit_shares_standard
+    );
+}
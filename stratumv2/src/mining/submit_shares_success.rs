@@ -0,0 +1,64 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// SubmitSharesSuccess is sent from the Server to the Client to acknowledge
+    /// accepted shares. The Server does not need to acknowledge every share
+    /// individually and MAY acknowledge a batch of submissions with a single
+    /// SubmitSharesSuccess.
+    SubmitSharesSuccess,
+
+    /// The channel that the shares were submitted on.
+    channel_id u32,
+
+    /// The most recent sequence number with a correct result.
+    last_sequence_number u32,
+
+    /// The count of new submissions acknowledged within this batch.
+    new_submits_accepted_count u32,
+
+    /// The sum of shares acknowledged within this batch.
+    new_shares_sum u64
+);
+
+impl SubmitSharesSuccess {
+    pub fn new(
+        channel_id: u32,
+        last_sequence_number: u32,
+        new_submits_accepted_count: u32,
+        new_shares_sum: u64,
+    ) -> Result<SubmitSharesSuccess> {
+        Ok(SubmitSharesSuccess {
+            channel_id,
+            last_sequence_number,
+            new_submits_accepted_count,
+            new_shares_sum,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_submit_shares_success() -> SubmitSharesSuccess {
+        SubmitSharesSuccess::new(1u32, 2u32, 3u32, 4u64).unwrap()
+    }
+
+    fn make_serialized_submit_shares_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x02, 0x00, 0x00, 0x00, // last_sequence_number
+            0x03, 0x00, 0x00, 0x00, // new_submits_accepted_count
+            0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // new_shares_sum
+        ];
+    }
+
+    impl_message_tests!(
+        SubmitSharesSuccess,
+        make_serialized_submit_shares_success,
+        make_deserialized_submit_shares_success
+    );
+}
@@ -0,0 +1,80 @@
+use crate::error::Result;
+use crate::impl_error_code_enum;
+use crate::impl_message;
+use crate::types::MessageType;
+
+/// Contains the error codes for the [UpdateChannelError](struct.UpdateChannelError.html)
+/// message. Each error code has a default STR0_255 message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateChannelErrorCode {
+    /// The max_target in the UpdateChannel message is not accepted by the Server.
+    MaxTargetOutOfRange,
+
+    /// The channel_id in the UpdateChannel message is unknown to the Server.
+    InvalidChannelId,
+}
+
+impl_error_code_enum!(
+    UpdateChannelErrorCode,
+    UpdateChannelErrorCode::MaxTargetOutOfRange => "max-target-out-of-range",
+    UpdateChannelErrorCode::InvalidChannelId => "invalid-channel-id"
+);
+
+impl_message!(
+    /// UpdateChannelError is sent from the Server to the Client in response to
+    /// a rejected [UpdateChannel](struct.UpdateChannel.html) message.
+    UpdateChannelError,
+
+    /// The channel_id received in the UpdateChannel message.
+    channel_id u32,
+
+    /// Pre-determined human readable error codes for the UpdateChannel message.
+    error_code UpdateChannelErrorCode
+);
+
+impl UpdateChannelError {
+    pub fn new(channel_id: u32, error_code: UpdateChannelErrorCode) -> Result<UpdateChannelError> {
+        Ok(UpdateChannelError {
+            channel_id,
+            error_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_update_channel_error() -> UpdateChannelError {
+        UpdateChannelError::new(1u32, UpdateChannelErrorCode::InvalidChannelId).unwrap()
+    }
+
+    fn make_serialized_update_channel_error() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // channel_id
+            0x12, 0x69, 0x6e, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x2d, 0x63, 0x68, 0x61, 0x6e, 0x6e,
+            0x65, 0x6c, 0x2d, 0x69, 0x64, // error_code
+        ];
+    }
+
+    impl_message_tests!(
+        UpdateChannelError,
+        make_serialized_update_channel_error,
+        make_deserialized_update_channel_error
+    );
+
+    #[test]
+    fn error_code_from_str() {
+        assert_eq!(
+            "max-target-out-of-range"
+                .parse::<UpdateChannelErrorCode>()
+                .unwrap(),
+            UpdateChannelErrorCode::MaxTargetOutOfRange
+        );
+        assert!(matches!(
+            "unknown-user".parse::<UpdateChannelErrorCode>(),
+            Err(Error::UnknownErrorCode { .. })
+        ));
+    }
+}
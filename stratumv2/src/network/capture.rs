@@ -0,0 +1,663 @@
+use crate::codec::{
+    deserialize, frame_size, ByteParser, Deserializable, Message, Serializable, FRAME_HEADER_SIZE,
+};
+use crate::error::{Error, Result};
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The magic bytes at the start of every capture file.
+const CAPTURE_MAGIC: [u8; 4] = *b"SV2C";
+
+/// The version of the capture file format.
+const CAPTURE_VERSION: u8 = 1;
+
+/// The size of the capture header: magic (4 bytes), version (U8) and
+/// start_time (U64).
+const CAPTURE_HEADER_SIZE: usize = 13;
+
+/// The size of a record before the network frame: direction (U8) and
+/// timestamp (U64).
+const RECORD_HEADER_SIZE: usize = 9;
+
+/// The direction of a captured message, relative to the device that recorded
+/// the capture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// The message was received from the counterparty.
+    Received,
+
+    /// The message was sent to the counterparty.
+    Sent,
+}
+
+impl Serializable for Direction {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        let byte: u8 = match self {
+            Direction::Received => 0,
+            Direction::Sent => 1,
+        };
+
+        byte.serialize(writer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+}
+
+impl Deserializable for Direction {
+    fn deserialize(parser: &mut ByteParser) -> Result<Direction> {
+        match u8::deserialize(parser)? {
+            0 => Ok(Direction::Received),
+            1 => Ok(Direction::Sent),
+            _ => Err(Error::DeserializationError(
+                "received unknown direction byte in capture record".into(),
+            )),
+        }
+    }
+}
+
+/// The header at the start of a capture file.
+struct CaptureHeader {
+    /// The unix time in microseconds when the capture was started.
+    start_time: u64,
+}
+
+impl Serializable for CaptureHeader {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_all(&CAPTURE_MAGIC)?;
+
+        Ok(CAPTURE_MAGIC.len()
+            + CAPTURE_VERSION.serialize(writer)?
+            + self.start_time.serialize(writer)?)
+    }
+
+    fn encoded_len(&self) -> usize {
+        CAPTURE_HEADER_SIZE
+    }
+}
+
+impl Deserializable for CaptureHeader {
+    fn deserialize(parser: &mut ByteParser) -> Result<CaptureHeader> {
+        if parser.next_by(CAPTURE_MAGIC.len())? != CAPTURE_MAGIC {
+            return Err(Error::DeserializationError(
+                "received unknown magic bytes in capture header".into(),
+            ));
+        }
+
+        let version = u8::deserialize(parser)?;
+        if version != CAPTURE_VERSION {
+            return Err(Error::VersionError(format!(
+                "unsupported capture version {}",
+                version
+            )));
+        }
+
+        Ok(CaptureHeader {
+            start_time: u64::deserialize(parser)?,
+        })
+    }
+}
+
+/// A plaintext message captured on a connection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureRecord {
+    pub direction: Direction,
+
+    /// The time the message was captured, relative to the start of the
+    /// capture. The timestamp is stored with microsecond precision.
+    pub timestamp: Duration,
+
+    pub message: Message,
+}
+
+impl CaptureRecord {
+    pub fn new(direction: Direction, timestamp: Duration, message: Message) -> CaptureRecord {
+        CaptureRecord {
+            direction,
+            timestamp,
+            message,
+        }
+    }
+}
+
+impl Serializable for CaptureRecord {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        Ok([
+            self.direction.serialize(writer)?,
+            (self.timestamp.as_micros() as u64).serialize(writer)?,
+            self.message.serialize(writer)?,
+        ]
+        .iter()
+        .sum())
+    }
+
+    fn encoded_len(&self) -> usize {
+        RECORD_HEADER_SIZE + self.message.encoded_len()
+    }
+}
+
+impl Deserializable for CaptureRecord {
+    fn deserialize(parser: &mut ByteParser) -> Result<CaptureRecord> {
+        Ok(CaptureRecord::new(
+            Direction::deserialize(parser)?,
+            Duration::from_micros(u64::deserialize(parser)?),
+            Message::deserialize(parser)?,
+        ))
+    }
+}
+
+/// CaptureWriter records the plaintext messages of a connection to a capture
+/// file, which can be read by a [CaptureReader](struct.CaptureReader.html).
+///
+/// A capture file starts with a header followed by a record for each message,
+/// all integers are little endian:
+///
+/// | Field      | Type    | Description                                         |
+/// |------------|---------|-----------------------------------------------------|
+/// | magic      | [u8; 4] | "SV2C"                                              |
+/// | version    | U8      | The version of the format, currently 1              |
+/// | start_time | U64     | Unix time in microseconds when the capture started  |
+///
+/// | Field      | Type    | Description                                         |
+/// |------------|---------|-----------------------------------------------------|
+/// | direction  | U8      | 0 if the message was received, 1 if it was sent     |
+/// | timestamp  | U64     | Microseconds since the start of the capture         |
+/// | frame      | Message | The network frame of the message                    |
+///
+/// # Examples
+///
+/// ```rust
+/// use stratumv2::codec::frame;
+/// use stratumv2::mining::CloseChannel;
+/// use stratumv2::network::{CaptureReader, CaptureWriter, Direction};
+///
+/// let message = frame(&CloseChannel::new(1, "shutdown").unwrap()).unwrap();
+///
+/// let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+/// writer.record(Direction::Sent, &message).unwrap();
+///
+/// let capture = writer.into_inner();
+/// let mut reader = CaptureReader::new(capture.as_slice()).unwrap();
+///
+/// let record = reader.next().unwrap().unwrap();
+/// assert_eq!(record.direction, Direction::Sent);
+/// assert_eq!(record.message, message);
+/// assert!(reader.next().is_none());
+/// ```
+pub struct CaptureWriter<W: io::Write> {
+    writer: W,
+    start: Instant,
+}
+
+impl<W: io::Write> CaptureWriter<W> {
+    /// Writes the capture header. The timestamps of the recorded messages are
+    /// relative to the creation of the CaptureWriter.
+    pub fn new(mut writer: W) -> Result<CaptureWriter<W>> {
+        let start_time = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+
+        CaptureHeader {
+            start_time: start_time.as_micros() as u64,
+        }
+        .serialize(&mut writer)?;
+
+        Ok(CaptureWriter {
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    /// Records a message with the time elapsed since the start of the
+    /// capture.
+    pub fn record(&mut self, direction: Direction, message: &Message) -> Result<()> {
+        direction.serialize(&mut self.writer)?;
+        (self.start.elapsed().as_micros() as u64).serialize(&mut self.writer)?;
+        message.serialize(&mut self.writer)?;
+
+        Ok(())
+    }
+
+    /// Writes a record with its own timestamp, for example to filter or
+    /// modify an existing capture.
+    pub fn write_record(&mut self, record: &CaptureRecord) -> Result<()> {
+        record.serialize(&mut self.writer)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// CaptureReader reads the records of a capture file written by a
+/// [CaptureWriter](struct.CaptureWriter.html). Records are read one at a time,
+/// so captures of long running connections are not loaded into memory.
+pub struct CaptureReader<R: io::Read> {
+    reader: R,
+    start_time: SystemTime,
+}
+
+impl<R: io::Read> CaptureReader<R> {
+    /// Reads and validates the capture header.
+    pub fn new(mut reader: R) -> Result<CaptureReader<R>> {
+        let mut header = [0u8; CAPTURE_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+
+        let header = deserialize::<CaptureHeader>(&header)?;
+
+        Ok(CaptureReader {
+            reader,
+            start_time: SystemTime::UNIX_EPOCH + Duration::from_micros(header.start_time),
+        })
+    }
+
+    /// Returns the time when the capture was started.
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    /// Reads the next record, None is returned at the end of the capture. An
+    /// error is returned if the capture ends in the middle of a record.
+    pub fn next_record(&mut self) -> Result<Option<CaptureRecord>> {
+        let mut buffer = vec![0u8; RECORD_HEADER_SIZE + FRAME_HEADER_SIZE];
+
+        // The capture can only end at the start of a record.
+        let read = loop {
+            match self.reader.read(&mut buffer[..1]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result?,
+            }
+        };
+        if read == 0 {
+            return Ok(None);
+        }
+
+        self.reader.read_exact(&mut buffer[1..])?;
+
+        // The frame header contains the length of the payload.
+        let frame_size = frame_size(&buffer[RECORD_HEADER_SIZE..]).unwrap_or(FRAME_HEADER_SIZE);
+        buffer.resize(RECORD_HEADER_SIZE + frame_size, 0);
+        self.reader
+            .read_exact(&mut buffer[RECORD_HEADER_SIZE + FRAME_HEADER_SIZE..])?;
+
+        Ok(Some(deserialize::<CaptureRecord>(&buffer)?))
+    }
+}
+
+impl<R: io::Read> Iterator for CaptureReader<R> {
+    type Item = Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// The timing used by a [Replayer](struct.Replayer.html) between the replayed
+/// records.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Timing {
+    /// Records are replayed at the time they were captured.
+    Original,
+
+    /// Records are replayed faster than they were captured by the given
+    /// factor, for example a factor of 10.0 replays a minute of capture in 6
+    /// seconds.
+    Accelerated(f64),
+
+    /// Records are replayed without waiting between them.
+    Immediate,
+}
+
+/// Replayer feeds the records of a capture back to a server or client
+/// implementation for regression testing. Each record is passed to a
+/// callback, which would typically pass the messages received by the
+/// recording device to the implementation and compare the sent messages to
+/// the responses of the implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// use stratumv2::codec::{frame, Message};
+/// use stratumv2::mining::CloseChannel;
+/// use stratumv2::network::{CaptureReader, CaptureWriter, Direction, Replayer, Timing};
+///
+/// let message = frame(&CloseChannel::new(1, "shutdown").unwrap()).unwrap();
+///
+/// let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+/// writer.record(Direction::Received, &message).unwrap();
+/// let capture = writer.into_inner();
+///
+/// let mut received: Vec<Message> = Vec::new();
+/// let replayer = Replayer::new(CaptureReader::new(capture.as_slice()).unwrap(), Timing::Immediate);
+/// replayer
+///     .replay(|record| {
+///         if record.direction == Direction::Received {
+///             received.push(record.message.clone());
+///         }
+///         Ok(())
+///     })
+///     .unwrap();
+///
+/// assert_eq!(received, vec![message]);
+/// ```
+pub struct Replayer<R: io::Read> {
+    reader: CaptureReader<R>,
+    timing: Timing,
+}
+
+impl<R: io::Read> Replayer<R> {
+    pub fn new(reader: CaptureReader<R>, timing: Timing) -> Replayer<R> {
+        Replayer { reader, timing }
+    }
+
+    /// Passes each record of the capture to the callback, waiting until the
+    /// time of the record according to the Timing. Replaying stops at the
+    /// first error returned by the callback. Returns the number of replayed
+    /// records.
+    pub fn replay<F>(self, mut callback: F) -> Result<usize>
+    where
+        F: FnMut(&CaptureRecord) -> Result<()>,
+    {
+        if let Timing::Accelerated(factor) = self.timing {
+            if !factor.is_finite() || factor <= 0.0 {
+                return Err(Error::RequirementError(
+                    "the accelerated timing factor MUST be a positive number".into(),
+                ));
+            }
+        }
+
+        let start = Instant::now();
+        let mut replayed = 0;
+
+        for record in self.reader {
+            let record = record?;
+
+            let due = match self.timing {
+                Timing::Original => Some(record.timestamp),
+                Timing::Accelerated(factor) => Some(record.timestamp.div_f64(factor)),
+                Timing::Immediate => None,
+            };
+
+            if let Some(wait) = due.and_then(|due| due.checked_sub(start.elapsed())) {
+                thread::sleep(wait);
+            }
+
+            callback(&record)?;
+            replayed += 1;
+        }
+
+        Ok(replayed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{frame, serialize};
+    use crate::mining::CloseChannel;
+    use crate::network::{ConnectionEncryptor, Peer};
+    use crate::types::MessageType;
+    use std::sync::{Arc, Mutex};
+
+    /// A writer that can be read while it is owned by a Peer.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A writer that fails once more than the capacity has been written.
+    struct LimitedWriter(usize);
+
+    impl io::Write for LimitedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if buf.len() > self.0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "capacity exceeded",
+                ));
+            }
+
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn make_message() -> Message {
+        frame(&CloseChannel::new(1, "shutdown").unwrap()).unwrap()
+    }
+
+    fn make_capture(records: &[CaptureRecord]) -> Vec<u8> {
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        for record in records {
+            writer.write_record(record).unwrap();
+        }
+
+        writer.into_inner()
+    }
+
+    #[test]
+    fn record_serde() {
+        let record = CaptureRecord::new(
+            Direction::Sent,
+            Duration::from_micros(0x0102),
+            Message::new(MessageType::OpenStandardMiningChannel, vec![0x05]),
+        );
+
+        let expected = vec![
+            0x01, // direction
+            0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // timestamp
+            0x00, 0x00, 0x10, 0x01, 0x00, 0x00, 0x05, // frame
+        ];
+
+        assert_eq!(serialize(&record).unwrap(), expected);
+        assert_eq!(record.encoded_len(), expected.len());
+        assert_eq!(deserialize::<CaptureRecord>(&expected).unwrap(), record);
+
+        assert!(matches!(
+            deserialize::<Direction>(&[0x02]),
+            Err(Error::DeserializationError { .. })
+        ));
+    }
+
+    #[test]
+    fn write_read_identity() {
+        let before = SystemTime::now();
+
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        writer.record(Direction::Received, &make_message()).unwrap();
+        writer.record(Direction::Sent, &make_message()).unwrap();
+        let capture = writer.into_inner();
+
+        let mut reader = CaptureReader::new(capture.as_slice()).unwrap();
+        assert!(reader.start_time() + Duration::from_micros(1) >= before);
+
+        let first = reader.next_record().unwrap().unwrap();
+        let second = reader.next_record().unwrap().unwrap();
+        assert_eq!(first.direction, Direction::Received);
+        assert_eq!(second.direction, Direction::Sent);
+        assert_eq!(first.message, make_message());
+        assert_eq!(second.message, make_message());
+        assert!(first.timestamp <= second.timestamp);
+
+        assert!(reader.next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn read_header_err() {
+        let capture = make_capture(&[]);
+
+        // Missing header.
+        assert!(matches!(
+            CaptureReader::new(&capture[..CAPTURE_HEADER_SIZE - 1]),
+            Err(Error::IOError { .. })
+        ));
+
+        // Unknown magic bytes.
+        let mut invalid = capture.clone();
+        invalid[0] = 0x00;
+        assert!(matches!(
+            CaptureReader::new(invalid.as_slice()),
+            Err(Error::DeserializationError { .. })
+        ));
+
+        // Unsupported version.
+        let mut invalid = capture.clone();
+        invalid[4] = CAPTURE_VERSION + 1;
+        assert!(matches!(
+            CaptureReader::new(invalid.as_slice()),
+            Err(Error::VersionError { .. })
+        ));
+    }
+
+    #[test]
+    fn read_truncated_record_err() {
+        let record = CaptureRecord::new(Direction::Sent, Duration::from_micros(1), make_message());
+        let capture = make_capture(&[record.clone()]);
+
+        // The capture ends in the record header, the frame header and the
+        // payload.
+        for len in &[1, RECORD_HEADER_SIZE + 1, record.encoded_len() - 1] {
+            let mut reader = CaptureReader::new(&capture[..CAPTURE_HEADER_SIZE + len]).unwrap();
+            assert!(matches!(reader.next_record(), Err(Error::IOError { .. })));
+        }
+    }
+
+    #[test]
+    fn peer_capture() {
+        let buffer = SharedBuffer::default();
+
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+        peer.start_capture(io::BufWriter::new(buffer.clone()))
+            .unwrap();
+
+        let received = Message::new(MessageType::OpenStandardMiningChannel, vec![0x01]);
+        assert_eq!(
+            peer.recv_msg(&serialize(&received).unwrap()).unwrap(),
+            received
+        );
+
+        peer.pending_msg_buffer.lock().unwrap().push(make_message());
+        assert_eq!(peer.get_pending_msgs(), vec![make_message()]);
+        assert!(peer.get_pending_msgs().is_empty());
+
+        // The buffered records are written when the capture is stopped.
+        assert!(buffer.0.lock().unwrap().is_empty());
+        assert!(peer.stop_capture().unwrap().is_some());
+        assert!(peer.stop_capture().unwrap().is_none());
+        assert!(peer.take_capture_error().is_none());
+
+        let capture = buffer.0.lock().unwrap().clone();
+        let records: Vec<CaptureRecord> = CaptureReader::new(capture.as_slice())
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].direction, Direction::Received);
+        assert_eq!(records[0].message, received);
+        assert_eq!(records[1].direction, Direction::Sent);
+        assert_eq!(records[1].message, make_message());
+    }
+
+    #[test]
+    fn peer_capture_err() {
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+        peer.start_capture(LimitedWriter(CAPTURE_HEADER_SIZE))
+            .unwrap();
+
+        // The messages are still sent when they can't be recorded, and the
+        // capture is disabled.
+        peer.pending_msg_buffer.lock().unwrap().push(make_message());
+        assert_eq!(peer.get_pending_msgs(), vec![make_message()]);
+        assert!(peer.capture.lock().unwrap().is_none());
+        assert!(matches!(
+            peer.take_capture_error(),
+            Some(Error::IOError { .. })
+        ));
+        assert!(peer.take_capture_error().is_none());
+
+        let received = make_message();
+        assert_eq!(
+            peer.recv_msg(&serialize(&received).unwrap()).unwrap(),
+            received
+        );
+
+        // Invalid frames are not recorded.
+        assert!(peer.recv_msg(&[0x00]).is_err());
+    }
+
+    #[test]
+    fn replay_timing() {
+        let records = vec![
+            CaptureRecord::new(
+                Direction::Received,
+                Duration::from_millis(0),
+                make_message(),
+            ),
+            CaptureRecord::new(Direction::Sent, Duration::from_millis(40), make_message()),
+        ];
+        let capture = make_capture(&records);
+
+        for (timing, min_elapsed) in &[
+            (Timing::Original, Duration::from_millis(40)),
+            (Timing::Accelerated(4.0), Duration::from_millis(10)),
+            (Timing::Immediate, Duration::from_millis(0)),
+        ] {
+            let reader = CaptureReader::new(capture.as_slice()).unwrap();
+            let start = Instant::now();
+
+            let mut replayed = Vec::new();
+            let count = Replayer::new(reader, *timing)
+                .replay(|record| {
+                    replayed.push(record.clone());
+                    Ok(())
+                })
+                .unwrap();
+
+            assert!(start.elapsed() >= *min_elapsed);
+            assert_eq!(count, 2);
+            assert_eq!(replayed, records);
+        }
+    }
+
+    #[test]
+    fn replay_err() {
+        let record = CaptureRecord::new(Direction::Sent, Duration::from_micros(1), make_message());
+        let capture = make_capture(&[record.clone(), record]);
+
+        let reader = CaptureReader::new(capture.as_slice()).unwrap();
+        assert!(matches!(
+            Replayer::new(reader, Timing::Accelerated(0.0)).replay(|_| Ok(())),
+            Err(Error::RequirementError { .. })
+        ));
+
+        // Replaying stops at the first error of the callback.
+        let reader = CaptureReader::new(capture.as_slice()).unwrap();
+        let mut calls = 0;
+        let result = Replayer::new(reader, Timing::Immediate).replay(|_| {
+            calls += 1;
+            Err(Error::Unimplemented())
+        });
+
+        assert!(matches!(result, Err(Error::Unimplemented())));
+        assert_eq!(calls, 1);
+    }
+}
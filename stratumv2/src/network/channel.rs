@@ -1,5 +1,14 @@
-use crate::mining::{OpenExtendedMiningChannel, OpenStandardMiningChannel};
-use std::{collections::HashMap, sync::Mutex};
+use crate::error::{Error, Result};
+use crate::mining::{
+    CloseChannel, OpenExtendedMiningChannel, OpenExtendedMiningChannelSuccess,
+    OpenStandardMiningChannel, OpenStandardMiningChannelSuccess, SetExtranoncePrefix,
+    SetGroupChannel,
+};
+use crate::types::B0_32;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
 
 use rand::Rng;
 
@@ -27,6 +36,75 @@ pub enum Channel {
     },
 }
 
+/// The maximum number of bytes of a full extranonce (prefix and the bytes
+/// rolled by the Client), as bounded by the B0_32 extranonce fields.
+const MAX_EXTRANONCE_LENGTH: usize = 32;
+
+impl Channel {
+    /// Creates a StandardMiningChannel from the OpenStandardMiningChannel
+    /// request and the OpenStandardMiningChannelSuccess response.
+    pub fn new_standard(
+        channel: OpenStandardMiningChannel,
+        success: &OpenStandardMiningChannelSuccess,
+    ) -> Channel {
+        Channel::StandardMiningChannel {
+            id: success.channel_id,
+            channel,
+        }
+    }
+
+    /// Creates an ExtendedMiningChannel from the OpenExtendedMiningChannel
+    /// request and the OpenExtendedMiningChannelSuccess response.
+    pub fn new_extended(
+        channel: OpenExtendedMiningChannel,
+        success: &OpenExtendedMiningChannelSuccess,
+    ) -> Channel {
+        Channel::ExtendedMiningChannel {
+            id: success.channel_id,
+            channel,
+        }
+    }
+
+    /// Returns the ChanID of the Channel.
+    pub fn id(&self) -> ChanID {
+        match self {
+            Channel::StandardMiningChannel { id, .. } => *id,
+            Channel::ExtendedMiningChannel { id, .. } => *id,
+        }
+    }
+}
+
+/// The extranonce assigned to a Channel by the Server. The Client rolls `size`
+/// bytes following the prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extranonce {
+    /// The extranonce prefix currently assigned to the channel by the Server.
+    pub prefix: B0_32,
+
+    /// The number of extranonce bytes the Client is able to roll. This is
+    /// negotiated when an extended channel is opened and is always 0 for a
+    /// standard channel.
+    pub size: u16,
+}
+
+impl Extranonce {
+    /// Creates the Extranonce assigned in an OpenStandardMiningChannelSuccess.
+    pub fn from_standard(success: &OpenStandardMiningChannelSuccess) -> Extranonce {
+        Extranonce {
+            prefix: success.extranonce_prefix.clone(),
+            size: 0,
+        }
+    }
+
+    /// Creates the Extranonce assigned in an OpenExtendedMiningChannelSuccess.
+    pub fn from_extended(success: &OpenExtendedMiningChannelSuccess) -> Extranonce {
+        Extranonce {
+            prefix: success.extranonce_prefix.clone(),
+            size: success.extranonce_size,
+        }
+    }
+}
+
 // TODO: Replace the u32 with a ConnID type.
 /// Holds a collection of [Channels](./enum.Channel.html) according to the ChanID and linked to a
 /// ConnID, representing the networked connection.
@@ -34,13 +112,304 @@ pub struct ChannelManager {
     /// Contains multiple channels that belong to a certain connection according
     /// to the Connection ID.
     pub channels: Mutex<HashMap<u32, HashMap<ChanID, Channel>>>,
+
+    /// Contains the group channels that belong to a certain connection
+    /// according to the Connection ID. Each group channel is a collection of
+    /// standard channels on the same connection. A standard channel can only
+    /// be a member of a single group.
+    pub groups: Mutex<HashMap<u32, HashMap<ChanID, HashSet<ChanID>>>>,
+
+    /// Contains the extranonce assigned to each channel that belongs to a
+    /// certain connection according to the Connection ID.
+    pub extranonces: Mutex<HashMap<u32, HashMap<ChanID, Extranonce>>>,
 }
 
 impl ChannelManager {
     pub fn new() -> Self {
         ChannelManager {
             channels: Mutex::new(HashMap::new()),
+            groups: Mutex::new(HashMap::new()),
+            extranonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Adds a Channel to a connection together with the Extranonce assigned
+    /// to it by the Server when the channel was opened.
+    pub fn add_channel(&self, conn_id: u32, channel: Channel, extranonce: Extranonce) {
+        let mut channels = self.channels.lock().unwrap();
+
+        self.extranonces
+            .lock()
+            .unwrap()
+            .entry(conn_id)
+            .or_default()
+            .insert(channel.id(), extranonce);
+
+        channels
+            .entry(conn_id)
+            .or_default()
+            .insert(channel.id(), channel);
+    }
+
+    /// Adds a StandardMiningChannel opened by the OpenStandardMiningChannelSuccess
+    /// to a connection.
+    pub fn add_standard_channel(
+        &self,
+        conn_id: u32,
+        channel: OpenStandardMiningChannel,
+        success: &OpenStandardMiningChannelSuccess,
+    ) {
+        self.add_channel(
+            conn_id,
+            Channel::new_standard(channel, success),
+            Extranonce::from_standard(success),
+        );
+    }
+
+    /// Adds an ExtendedMiningChannel opened by the OpenExtendedMiningChannelSuccess
+    /// to a connection.
+    pub fn add_extended_channel(
+        &self,
+        conn_id: u32,
+        channel: OpenExtendedMiningChannel,
+        success: &OpenExtendedMiningChannelSuccess,
+    ) {
+        self.add_channel(
+            conn_id,
+            Channel::new_extended(channel, success),
+            Extranonce::from_extended(success),
+        );
+    }
+
+    /// Removes a Channel from a connection and from any group channel it is a
+    /// member of. The removed Channel is returned.
+    pub fn remove_channel(&self, conn_id: u32, channel_id: ChanID) -> Result<Channel> {
+        let mut channels = self.channels.lock().unwrap();
+        let channel = channels
+            .get_mut(&conn_id)
+            .and_then(|c| c.remove(&channel_id))
+            .ok_or(Error::UnknownChannelId(channel_id))?;
+
+        if let Some(conn_groups) = self.groups.lock().unwrap().get_mut(&conn_id) {
+            for members in conn_groups.values_mut() {
+                members.remove(&channel_id);
+            }
+        }
+
+        if let Some(conn_extranonces) = self.extranonces.lock().unwrap().get_mut(&conn_id) {
+            conn_extranonces.remove(&channel_id);
+        }
+
+        Ok(channel)
+    }
+
+    /// Applies a received [CloseChannel](../mining/struct.CloseChannel.html)
+    /// message. If the channel_id identifies a group channel, the group and
+    /// all of its member channels are closed. The closed Channels are returned.
+    pub fn apply_close_channel(&self, conn_id: u32, msg: &CloseChannel) -> Result<Vec<Channel>> {
+        let group = self
+            .groups
+            .lock()
+            .unwrap()
+            .get_mut(&conn_id)
+            .and_then(|g| g.remove(&msg.channel_id));
+
+        match group {
+            Some(members) => {
+                let mut members: Vec<ChanID> = members.into_iter().collect();
+                members.sort_unstable();
+
+                members
+                    .into_iter()
+                    .map(|channel_id| self.remove_channel(conn_id, channel_id))
+                    .collect()
+            }
+            None => Ok(vec![self.remove_channel(conn_id, msg.channel_id)?]),
+        }
+    }
+
+    /// Sets the extranonce assigned to a Channel on a connection.
+    pub fn set_extranonce(
+        &self,
+        conn_id: u32,
+        channel_id: ChanID,
+        extranonce: Extranonce,
+    ) -> Result<()> {
+        let channels = self.channels.lock().unwrap();
+        if !channels
+            .get(&conn_id)
+            .is_some_and(|c| c.contains_key(&channel_id))
+        {
+            return Err(Error::UnknownChannelId(channel_id));
+        }
+
+        self.extranonces
+            .lock()
+            .unwrap()
+            .entry(conn_id)
+            .or_default()
+            .insert(channel_id, extranonce);
+
+        Ok(())
+    }
+
+    /// Returns the extranonce assigned to a Channel on a connection.
+    pub fn extranonce(&self, conn_id: u32, channel_id: ChanID) -> Option<Extranonce> {
+        self.extranonces
+            .lock()
+            .unwrap()
+            .get(&conn_id)
+            .and_then(|e| e.get(&channel_id))
+            .cloned()
+    }
+
+    /// Applies a [SetExtranoncePrefix](../mining/struct.SetExtranoncePrefix.html)
+    /// message to a standard or extended channel. The new prefix together with
+    /// the channel's extranonce size MUST fit in a full extranonce, otherwise
+    /// the Client would be unable to roll its extranonce space.
+    pub fn set_extranonce_prefix(&self, conn_id: u32, msg: &SetExtranoncePrefix) -> Result<()> {
+        let channels = self.channels.lock().unwrap();
+        if !channels
+            .get(&conn_id)
+            .is_some_and(|c| c.contains_key(&msg.channel_id))
+        {
+            return Err(Error::UnknownChannelId(msg.channel_id));
+        }
+
+        let mut extranonces = self.extranonces.lock().unwrap();
+        let extranonce = extranonces
+            .get_mut(&conn_id)
+            .and_then(|e| e.get_mut(&msg.channel_id))
+            .ok_or_else(|| {
+                Error::RequirementError(
+                    "the channel has no extranonce assigned by the Server".into(),
+                )
+            })?;
+
+        if msg.extranonce_prefix.data.len() + extranonce.size as usize > MAX_EXTRANONCE_LENGTH {
+            return Err(Error::RequirementError(
+                "extranonce prefix and extranonce_size cannot be greater than the maximum extranonce length".into(),
+            ));
+        }
+
+        extranonce.prefix = msg.extranonce_prefix.clone();
+
+        Ok(())
+    }
+
+    /// Creates a new empty group channel on a connection and returns its id.
+    /// The id is guaranteed not to collide with any other channel or group
+    /// channel on the connection.
+    pub fn create_group(&self, conn_id: u32) -> ChanID {
+        let channels = self.channels.lock().unwrap();
+        let mut groups = self.groups.lock().unwrap();
+        let conn_groups = groups.entry(conn_id).or_default();
+
+        let mut group_id = new_channel_id();
+        while conn_groups.contains_key(&group_id)
+            || channels
+                .get(&conn_id)
+                .is_some_and(|c| c.contains_key(&group_id))
+        {
+            group_id = new_channel_id();
+        }
+
+        conn_groups.insert(group_id, HashSet::new());
+        group_id
+    }
+
+    /// Adds a standard channel to a group channel on a connection. The group
+    /// channel is created if it doesn't exist, allowing Clients to track the
+    /// group_channel_id assigned by the Server. If the standard channel is
+    /// already a member of another group, it is removed from that group.
+    pub fn add_to_group(&self, conn_id: u32, group_id: ChanID, channel_id: ChanID) -> Result<()> {
+        let channels = self.channels.lock().unwrap();
+        match channels.get(&conn_id).and_then(|c| c.get(&channel_id)) {
+            Some(Channel::StandardMiningChannel { .. }) => (),
+            Some(_) => {
+                return Err(Error::RequirementError(
+                    "only standard channels can be a member of a group channel".into(),
+                ))
+            }
+            None => return Err(Error::UnknownChannelId(channel_id)),
+        }
+
+        let mut groups = self.groups.lock().unwrap();
+        let conn_groups = groups.entry(conn_id).or_default();
+
+        for members in conn_groups.values_mut() {
+            members.remove(&channel_id);
+        }
+
+        conn_groups.entry(group_id).or_default().insert(channel_id);
+
+        Ok(())
+    }
+
+    /// Removes a standard channel from a group channel on a connection.
+    pub fn remove_from_group(
+        &self,
+        conn_id: u32,
+        group_id: ChanID,
+        channel_id: ChanID,
+    ) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        let members = groups
+            .get_mut(&conn_id)
+            .and_then(|g| g.get_mut(&group_id))
+            .ok_or(Error::UnknownGroupChannelId(group_id))?;
+
+        if !members.remove(&channel_id) {
+            return Err(Error::UnknownChannelId(channel_id));
         }
+
+        Ok(())
+    }
+
+    /// Resolves a group channel to the ids of its member standard channels,
+    /// allowing a job to be broadcast once per group.
+    pub fn group_members(&self, conn_id: u32, group_id: ChanID) -> Result<Vec<ChanID>> {
+        let groups = self.groups.lock().unwrap();
+        let members = groups
+            .get(&conn_id)
+            .and_then(|g| g.get(&group_id))
+            .ok_or(Error::UnknownGroupChannelId(group_id))?;
+
+        let mut members: Vec<ChanID> = members.iter().copied().collect();
+        members.sort_unstable();
+
+        Ok(members)
+    }
+
+    /// Returns the group channel that a standard channel is a member of.
+    pub fn group_of(&self, conn_id: u32, channel_id: ChanID) -> Option<ChanID> {
+        self.groups.lock().unwrap().get(&conn_id).and_then(|g| {
+            g.iter()
+                .find(|(_, members)| members.contains(&channel_id))
+                .map(|(group_id, _)| *group_id)
+        })
+    }
+
+    /// Applies a received [SetGroupChannel](../mining/struct.SetGroupChannel.html)
+    /// message, reassigning each of the standard channels to the group.
+    pub fn apply_set_group_channel(&self, conn_id: u32, msg: &SetGroupChannel) -> Result<()> {
+        for channel_id in msg.channel_ids.data.iter() {
+            self.add_to_group(conn_id, msg.group_channel_id, *channel_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds a [SetGroupChannel](../mining/struct.SetGroupChannel.html)
+    /// message announcing the current members of a group channel.
+    pub fn set_group_channel_msg(&self, conn_id: u32, group_id: ChanID) -> Result<SetGroupChannel> {
+        SetGroupChannel::new(group_id, self.group_members(conn_id, group_id)?)
+    }
+}
+
+impl Default for ChannelManager {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -48,8 +417,227 @@ impl ChannelManager {
 mod tests {
     use super::*;
 
+    fn add_standard(manager: &ChannelManager, conn_id: u32, id: ChanID) {
+        manager.add_standard_channel(
+            conn_id,
+            OpenStandardMiningChannel::new(1, "user id", 3.0, [4u8; 32]).unwrap(),
+            &OpenStandardMiningChannelSuccess::new(1, id, [4u8; 32], [5u8; 4], 0).unwrap(),
+        );
+    }
+
+    fn add_extended(manager: &ChannelManager, conn_id: u32, id: ChanID) {
+        manager.add_extended_channel(
+            conn_id,
+            OpenExtendedMiningChannel::new(1, "user id", 3.0, [4u8; 32], 4).unwrap(),
+            &extended_success(id),
+        );
+    }
+
+    fn extended_success(id: ChanID) -> OpenExtendedMiningChannelSuccess {
+        OpenExtendedMiningChannelSuccess::new(1, id, [4u8; 32], 8, [5u8; 4]).unwrap()
+    }
+
     #[test]
     fn channel_id_generate() {
         assert!(new_channel_id() <= u32::MAX)
     }
+
+    #[test]
+    fn group_add_and_resolve() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+        add_standard(&manager, 0, 2);
+
+        let group_id = manager.create_group(0);
+        assert_eq!(
+            manager.group_members(0, group_id).unwrap(),
+            Vec::<ChanID>::new()
+        );
+
+        manager.add_to_group(0, group_id, 2).unwrap();
+        manager.add_to_group(0, group_id, 1).unwrap();
+        assert_eq!(manager.group_members(0, group_id).unwrap(), vec![1, 2]);
+        assert_eq!(manager.group_of(0, 1), Some(group_id));
+
+        // Groups are scoped to a connection.
+        assert!(matches!(
+            manager.group_members(1, group_id),
+            Err(Error::UnknownGroupChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn group_remove() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+        manager.add_to_group(0, 10, 1).unwrap();
+
+        manager.remove_from_group(0, 10, 1).unwrap();
+        assert_eq!(manager.group_members(0, 10).unwrap(), Vec::<ChanID>::new());
+        assert_eq!(manager.group_of(0, 1), None);
+
+        assert!(matches!(
+            manager.remove_from_group(0, 10, 1),
+            Err(Error::UnknownChannelId { .. })
+        ));
+        assert!(matches!(
+            manager.remove_from_group(0, 11, 1),
+            Err(Error::UnknownGroupChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn group_single_membership() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+
+        manager.add_to_group(0, 10, 1).unwrap();
+        manager.add_to_group(0, 11, 1).unwrap();
+        assert_eq!(manager.group_members(0, 10).unwrap(), Vec::<ChanID>::new());
+        assert_eq!(manager.group_members(0, 11).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn group_only_standard_channels() {
+        let manager = ChannelManager::new();
+        add_extended(&manager, 0, 1);
+
+        assert!(matches!(
+            manager.add_to_group(0, 10, 1),
+            Err(Error::RequirementError { .. })
+        ));
+        assert!(matches!(
+            manager.add_to_group(0, 10, 2),
+            Err(Error::UnknownChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn close_channel() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+        add_standard(&manager, 0, 2);
+        manager.add_to_group(0, 10, 1).unwrap();
+        manager.add_to_group(0, 10, 2).unwrap();
+
+        let msg = CloseChannel::new(1, "").unwrap();
+        let closed = manager.apply_close_channel(0, &msg).unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].id(), 1);
+        assert!(!manager.channels.lock().unwrap()[&0].contains_key(&1));
+        assert_eq!(manager.group_members(0, 10).unwrap(), vec![2]);
+
+        assert!(matches!(
+            manager.apply_close_channel(0, &msg),
+            Err(Error::UnknownChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn close_group_channel() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+        add_standard(&manager, 0, 2);
+        add_standard(&manager, 0, 3);
+        manager.add_to_group(0, 10, 1).unwrap();
+        manager.add_to_group(0, 10, 2).unwrap();
+
+        let msg = CloseChannel::new(10, "").unwrap();
+        let closed = manager.apply_close_channel(0, &msg).unwrap();
+        assert_eq!(
+            closed.iter().map(|c| c.id()).collect::<Vec<ChanID>>(),
+            vec![1, 2]
+        );
+        assert_eq!(manager.channels.lock().unwrap()[&0].len(), 1);
+        assert!(matches!(
+            manager.group_members(0, 10),
+            Err(Error::UnknownGroupChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn extranonce_prefix() {
+        let manager = ChannelManager::new();
+        add_extended(&manager, 0, 1);
+        assert_eq!(
+            manager.extranonce(0, 1).unwrap(),
+            Extranonce {
+                prefix: B0_32::new([5u8; 4]).unwrap(),
+                size: 8,
+            }
+        );
+
+        let msg = SetExtranoncePrefix::new(1, [6u8; 24]).unwrap();
+        manager.set_extranonce_prefix(0, &msg).unwrap();
+        assert_eq!(
+            manager.extranonce(0, 1).unwrap(),
+            Extranonce {
+                prefix: B0_32::new([6u8; 24]).unwrap(),
+                size: 8,
+            }
+        );
+
+        // The extranonce is removed with the channel.
+        manager.remove_channel(0, 1).unwrap();
+        assert_eq!(manager.extranonce(0, 1), None);
+    }
+
+    #[test]
+    fn extranonce_prefix_standard_channel() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+
+        // Standard channels don't roll any extranonce bytes, so the prefix can
+        // be a full extranonce.
+        let msg = SetExtranoncePrefix::new(1, [6u8; 32]).unwrap();
+        manager.set_extranonce_prefix(0, &msg).unwrap();
+        assert_eq!(manager.extranonce(0, 1).unwrap().size, 0);
+        assert_eq!(manager.extranonce(0, 1).unwrap().prefix, vec![6u8; 32]);
+    }
+
+    #[test]
+    fn extranonce_prefix_errors() {
+        let manager = ChannelManager::new();
+        add_extended(&manager, 0, 1);
+
+        // The prefix leaves no room for the negotiated extranonce_size, even
+        // though it leaves room for the requested min_extranonce_size.
+        let msg = SetExtranoncePrefix::new(1, [6u8; 25]).unwrap();
+        assert!(matches!(
+            manager.set_extranonce_prefix(0, &msg),
+            Err(Error::RequirementError { .. })
+        ));
+        assert_eq!(manager.extranonce(0, 1).unwrap().prefix, vec![5u8; 4]);
+
+        // The extranonce size isn't guessed for a channel without an
+        // extranonce.
+        manager.extranonces.lock().unwrap().clear();
+        let msg = SetExtranoncePrefix::new(1, [6u8; 4]).unwrap();
+        assert!(matches!(
+            manager.set_extranonce_prefix(0, &msg),
+            Err(Error::RequirementError { .. })
+        ));
+
+        let msg = SetExtranoncePrefix::new(3, [6u8; 4]).unwrap();
+        assert!(matches!(
+            manager.set_extranonce_prefix(0, &msg),
+            Err(Error::UnknownChannelId { .. })
+        ));
+        assert!(matches!(
+            manager.set_extranonce(0, 3, Extranonce::from_extended(&extended_success(3))),
+            Err(Error::UnknownChannelId { .. })
+        ));
+    }
+
+    #[test]
+    fn group_set_group_channel() {
+        let manager = ChannelManager::new();
+        add_standard(&manager, 0, 1);
+        add_standard(&manager, 0, 2);
+
+        let msg = SetGroupChannel::new(10, vec![1, 2]).unwrap();
+        manager.apply_set_group_channel(0, &msg).unwrap();
+        assert_eq!(manager.group_members(0, 10).unwrap(), vec![1, 2]);
+        assert_eq!(manager.set_group_channel_msg(0, 10).unwrap(), msg);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::noise::{SignatureNoiseMessage, StaticKeyPair};
+use std::time::Duration;
 
 /// NoiseConfig contains the configuration for devices to assign a pre-defined
 /// StaticKeyPair and SignatureNoiseMessage signed by the Certificate Authority
@@ -66,3 +67,54 @@ impl ServerConfig {
         ServerConfig { mining_flags }
     }
 }
+
+/// JobNegotiatorConfig contains the pool policy used by a
+/// [JobNegotiator](struct.JobNegotiator.html) when allocating mining_job_tokens
+/// and validating committed mining jobs.
+#[derive(Clone)]
+pub struct JobNegotiatorConfig {
+    /// Serialized transaction outputs that the pool requires in the coinbase
+    /// transaction of every committed mining job.
+    pub coinbase_output: Vec<u8>,
+
+    /// The maximum size, in bytes, of the coinbase transaction outputs a Job
+    /// Negotiator may add in addition to the coinbase_output.
+    pub coinbase_output_max_additional_size: u32,
+
+    /// Whether a mining_job_token can be used on a mining connection before the
+    /// mining job has been committed. Connections that set
+    /// REQUIRES_ASYNC_JOB_MINING are rejected if this is false.
+    pub async_mining_allowed: bool,
+
+    /// The duration a mining_job_token remains valid after it is allocated or
+    /// committed. Expired tokens are removed and can no longer be used.
+    pub token_ttl: Duration,
+
+    /// The maximum number of unexpired mining_job_tokens a single connection
+    /// can hold. Further AllocateMiningJobToken messages are rejected until
+    /// tokens expire.
+    pub max_tokens_per_connection: usize,
+}
+
+impl JobNegotiatorConfig {
+    /// The default token_ttl of 10 minutes, long enough to negotiate a job for
+    /// the current block.
+    pub const DEFAULT_TOKEN_TTL: Duration = Duration::from_secs(600);
+
+    /// The default max_tokens_per_connection.
+    pub const DEFAULT_MAX_TOKENS_PER_CONNECTION: usize = 64;
+
+    pub fn new<T: Into<Vec<u8>>>(
+        coinbase_output: T,
+        coinbase_output_max_additional_size: u32,
+        async_mining_allowed: bool,
+    ) -> Self {
+        JobNegotiatorConfig {
+            coinbase_output: coinbase_output.into(),
+            coinbase_output_max_additional_size,
+            async_mining_allowed,
+            token_ttl: Self::DEFAULT_TOKEN_TTL,
+            max_tokens_per_connection: Self::DEFAULT_MAX_TOKENS_PER_CONNECTION,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::mining::Reconnect;
+use std::fmt;
+
+/// Endpoint is the host and port of an Upstream Node that a downstream device
+/// connects to. A Client can use the Endpoint of its current connection to
+/// resolve the target of a received [Reconnect](../mining/struct.Reconnect.html)
+/// message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    /// The hostname or IP address of the Upstream Node.
+    pub host: String,
+
+    /// The port of the Upstream Node.
+    pub port: u16,
+}
+
+impl Endpoint {
+    pub fn new<T: Into<String>>(host: T, port: u16) -> Self {
+        Endpoint {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Returns the Endpoint the Client should connect to after receiving a
+    /// Reconnect message on a connection to this Endpoint. An empty new_host
+    /// keeps the present host and a new_port of 0 keeps the present port.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stratumv2::mining::Reconnect;
+    /// use stratumv2::network::Endpoint;
+    ///
+    /// let current = Endpoint::new("pool.example.com", 3336);
+    ///
+    /// let reconnect = Reconnect::new("", 3337).unwrap();
+    /// assert_eq!(
+    ///     current.reconnect_target(&reconnect),
+    ///     Endpoint::new("pool.example.com", 3337)
+    /// );
+    /// ```
+    pub fn reconnect_target(&self, reconnect: &Reconnect) -> Endpoint {
+        let host = if reconnect.new_host.data.is_empty() {
+            self.host.clone()
+        } else {
+            reconnect.new_host.data.clone()
+        };
+
+        let port = if reconnect.new_port == 0 {
+            self.port
+        } else {
+            reconnect.new_port
+        };
+
+        Endpoint { host, port }
+    }
+}
+
+/// Display formats the Endpoint as an address that can be passed to a socket
+/// connect call. IPv6 hosts are enclosed in square brackets.
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.host.contains(':') && !self.host.starts_with('[') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnect_new_host_and_port() {
+        let current = Endpoint::new("0.0.0.0", 8545);
+        let reconnect = Reconnect::new("127.0.0.1", 3336).unwrap();
+        assert_eq!(
+            current.reconnect_target(&reconnect),
+            Endpoint::new("127.0.0.1", 3336)
+        );
+    }
+
+    #[test]
+    fn reconnect_present_host() {
+        let current = Endpoint::new("0.0.0.0", 8545);
+        let reconnect = Reconnect::new("", 3336).unwrap();
+        assert_eq!(
+            current.reconnect_target(&reconnect),
+            Endpoint::new("0.0.0.0", 3336)
+        );
+    }
+
+    #[test]
+    fn reconnect_present_port() {
+        let current = Endpoint::new("0.0.0.0", 8545);
+        let reconnect = Reconnect::new("127.0.0.1", 0).unwrap();
+        assert_eq!(
+            current.reconnect_target(&reconnect),
+            Endpoint::new("127.0.0.1", 8545)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Endpoint::new("0.0.0.0", 8545).to_string(), "0.0.0.0:8545");
+        assert_eq!(Endpoint::new("::1", 8545).to_string(), "[::1]:8545");
+        assert_eq!(Endpoint::new("[::1]", 8545).to_string(), "[::1]:8545");
+    }
+}
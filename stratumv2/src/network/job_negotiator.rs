@@ -0,0 +1,666 @@
+use crate::codec::frame;
+use crate::common::{SetupConnection, SetupConnectionErrorCode};
+use crate::error::{Error, Result};
+use crate::job_negotiation;
+use crate::job_negotiation::{
+    AllocateMiningJobToken, AllocateMiningJobTokenSuccess, CommitMiningJob, CommitMiningJobError,
+    CommitMiningJobErrorCode, CommitMiningJobSuccess, SetupConnectionError, SetupConnectionFlags,
+    SetupConnectionSuccess, SetupConnectionSuccessFlags,
+};
+use crate::network::{Encryptor, JobNegotiationReceiver, JobNegotiatorConfig, Peer};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use bitcoin::{consensus::encode::deserialize_partial, TxOut};
+use rand::Rng;
+
+/// The only version of the protocol supported by the JobNegotiator.
+const PROTOCOL_VERSION: u16 = 2;
+
+/// The number of random bytes used for each allocated mining_job_token.
+const MINING_JOB_TOKEN_SIZE: usize = 8;
+
+/// The lifecycle state of a mining_job_token allocated by a
+/// [JobNegotiator](struct.JobNegotiator.html).
+#[derive(Debug, Clone, PartialEq)]
+pub enum MiningJobTokenState {
+    /// The token was sent in an AllocateMiningJobTokenSuccess message and has
+    /// not yet been used to commit a mining job. If async_mining_allowed is
+    /// true, the token can already be used on a mining connection.
+    Allocated { async_mining_allowed: bool },
+
+    /// The token was sent in a CommitMiningJobSuccess message and identifies a
+    /// mining job that was accepted by the pool.
+    Committed,
+}
+
+/// A mining_job_token tracked by a JobNegotiator.
+struct MiningJobToken {
+    state: MiningJobTokenState,
+    expires_at: Instant,
+}
+
+impl MiningJobToken {
+    fn new(state: MiningJobTokenState, ttl: Duration) -> MiningJobToken {
+        MiningJobToken {
+            state,
+            expires_at: Instant::now() + ttl,
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Deserializes a sequence of serialized transaction outputs. None is returned
+/// if the bytes are not a sequence of whole transaction outputs.
+fn deserialize_outputs(mut bytes: &[u8]) -> Option<Vec<TxOut>> {
+    let mut outputs = Vec::new();
+    while !bytes.is_empty() {
+        let (output, length) = deserialize_partial::<TxOut>(bytes).ok()?;
+        outputs.push(output);
+        bytes = &bytes[length..];
+    }
+
+    Some(outputs)
+}
+
+/// JobNegotiator is the pool side of the Job Negotiation protocol. It
+/// allocates mining_job_tokens, validates committed mining jobs against the
+/// pool policy in the [JobNegotiatorConfig](struct.JobNegotiatorConfig.html)
+/// and tracks the state of every token. Tokens expire after the token_ttl of
+/// the config and each connection can hold at most max_tokens_per_connection
+/// unexpired tokens.
+///
+/// The mining connections of the pool can use
+/// [is_token_usable](struct.JobNegotiator.html#method.is_token_usable) to
+/// check the mining_job_token of a SetCustomMiningJob message.
+pub struct JobNegotiator {
+    pub config: JobNegotiatorConfig,
+
+    /// Contains every mining_job_token that has been allocated or committed
+    /// and not yet removed.
+    tokens: Mutex<HashMap<Vec<u8>, MiningJobToken>>,
+}
+
+impl JobNegotiator {
+    pub fn new(config: JobNegotiatorConfig) -> Self {
+        JobNegotiator {
+            config,
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Generates a new random mining_job_token that does not collide with an
+    /// existing token and stores it with the given state.
+    fn insert_token(
+        &self,
+        tokens: &mut HashMap<Vec<u8>, MiningJobToken>,
+        state: MiningJobTokenState,
+    ) -> Vec<u8> {
+        let mut rng = rand::thread_rng();
+
+        let mut token = rng.gen::<[u8; MINING_JOB_TOKEN_SIZE]>().to_vec();
+        while tokens.contains_key(&token) {
+            token = rng.gen::<[u8; MINING_JOB_TOKEN_SIZE]>().to_vec();
+        }
+
+        tokens.insert(
+            token.clone(),
+            MiningJobToken::new(state, self.config.token_ttl),
+        );
+        token
+    }
+
+    /// Returns the state of a mining_job_token, None is returned if the token
+    /// is unknown or expired.
+    pub fn token_state(&self, token: &[u8]) -> Option<MiningJobTokenState> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .get(token)
+            .filter(|t| !t.is_expired(Instant::now()))
+            .map(|t| t.state.clone())
+    }
+
+    /// Returns true if the mining_job_token can be used on a mining
+    /// connection. This is the case for committed tokens and for allocated
+    /// tokens where asynchronous mining was allowed.
+    pub fn is_token_usable(&self, token: &[u8]) -> bool {
+        match self.token_state(token) {
+            Some(MiningJobTokenState::Committed) => true,
+            Some(MiningJobTokenState::Allocated {
+                async_mining_allowed,
+            }) => async_mining_allowed,
+            None => false,
+        }
+    }
+
+    /// Removes a mining_job_token, for example when the job it identifies is
+    /// no longer valid. The state of the removed token is returned.
+    pub fn remove_token(&self, token: &[u8]) -> Option<MiningJobTokenState> {
+        self.tokens.lock().unwrap().remove(token).map(|t| t.state)
+    }
+
+    /// Removes every expired mining_job_token. Expired tokens are also removed
+    /// whenever a token is allocated or committed.
+    pub fn remove_expired_tokens(&self) {
+        let now = Instant::now();
+        self.tokens
+            .lock()
+            .unwrap()
+            .retain(|_, token| !token.is_expired(now));
+    }
+
+    /// Validates a CommitMiningJob message against the state of its
+    /// mining_job_token and the pool policy. The error code that should be
+    /// returned to the Client is returned if the mining job is rejected.
+    pub fn validate_commit_mining_job(
+        &self,
+        msg: &CommitMiningJob,
+    ) -> std::result::Result<(), CommitMiningJobErrorCode> {
+        self.validate_commit(&self.tokens.lock().unwrap(), msg)
+    }
+
+    fn validate_commit(
+        &self,
+        tokens: &HashMap<Vec<u8>, MiningJobToken>,
+        msg: &CommitMiningJob,
+    ) -> std::result::Result<(), CommitMiningJobErrorCode> {
+        match tokens.get(&msg.mining_job_token.data) {
+            Some(MiningJobToken {
+                state: MiningJobTokenState::Allocated { .. },
+                expires_at,
+            }) if Instant::now() < *expires_at => (),
+            _ => return Err(CommitMiningJobErrorCode::InvalidMiningJobToken),
+        }
+
+        // Every output required by the pool must be one of the committed
+        // outputs, compared as whole outputs rather than bytes.
+        let mut outputs = deserialize_outputs(&msg.coinbase_tx_outputs.data)
+            .ok_or(CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs)?;
+        let required = deserialize_outputs(&self.config.coinbase_output)
+            .ok_or(CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs)?;
+
+        for output in required.iter() {
+            match outputs.iter().position(|o| o == output) {
+                Some(i) => outputs.remove(i),
+                None => {
+                    return Err(CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs)
+                }
+            };
+        }
+
+        let additional_size =
+            msg.coinbase_tx_outputs.data.len() - self.config.coinbase_output.len();
+        if additional_size > self.config.coinbase_output_max_additional_size as usize {
+            return Err(CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs);
+        }
+
+        Ok(())
+    }
+
+    /// Validates a CommitMiningJob message and replaces its allocated
+    /// mining_job_token with a new committed token. The tokens are checked and
+    /// replaced under a single lock, so an allocated token can only be
+    /// committed once.
+    fn commit_mining_job(
+        &self,
+        msg: &CommitMiningJob,
+    ) -> std::result::Result<Vec<u8>, CommitMiningJobErrorCode> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let now = Instant::now();
+        tokens.retain(|_, token| !token.is_expired(now));
+
+        self.validate_commit(&tokens, msg)?;
+        tokens
+            .remove(&msg.mining_job_token.data)
+            .ok_or(CommitMiningJobErrorCode::InvalidMiningJobToken)?;
+
+        Ok(self.insert_token(&mut tokens, MiningJobTokenState::Committed))
+    }
+}
+
+/// Returns an error if a Job Negotiation SetupConnection message has not been
+/// accepted on the connection.
+fn check_setup_conn<E: Encryptor>(peer: &Peer<E>) -> Result<()> {
+    match &peer.setup_conn_msg {
+        Some(SetupConnection::JobNegotiation(_)) => Ok(()),
+        _ => Err(Error::RequirementError(
+            "a job negotiation connection MUST be setup before negotiating jobs".into(),
+        )),
+    }
+}
+
+impl<E: Encryptor> JobNegotiationReceiver<E> for JobNegotiator {
+    fn handle_jn_new_conn(
+        &self,
+        new_conn: job_negotiation::SetupConnection,
+        peer: &mut Peer<E>,
+    ) -> Result<()> {
+        let mut supported_flags = SetupConnectionFlags::empty();
+        if self.config.async_mining_allowed {
+            supported_flags |= SetupConnectionFlags::REQUIRES_ASYNC_JOB_MINING;
+        }
+
+        let unsupported_flags = new_conn.flags - supported_flags;
+        let error_code = if !unsupported_flags.is_empty() {
+            Some(SetupConnectionErrorCode::UnsupportedFeatureFlags)
+        } else if new_conn.min_version > PROTOCOL_VERSION || new_conn.max_version < PROTOCOL_VERSION
+        {
+            Some(SetupConnectionErrorCode::ProtocolVersionMismatch)
+        } else {
+            None
+        };
+
+        let msg = match error_code {
+            Some(error_code) => frame(&SetupConnectionError::new(unsupported_flags, error_code)?)?,
+            None => {
+                let success = SetupConnectionSuccess::new(
+                    PROTOCOL_VERSION,
+                    SetupConnectionSuccessFlags::NONE,
+                )?;
+                peer.setup_conn_msg = Some(SetupConnection::JobNegotiation(new_conn));
+                frame(&success)?
+            }
+        };
+
+        peer.pending_msg_buffer.lock().unwrap().push(msg);
+        Ok(())
+    }
+
+    fn handle_allocate_mining_job_token(
+        &self,
+        msg: AllocateMiningJobToken,
+        peer: &mut Peer<E>,
+    ) -> Result<()> {
+        check_setup_conn(peer)?;
+
+        let async_mining_allowed = self.config.async_mining_allowed;
+        let token = {
+            let mut tokens = self.tokens.lock().unwrap();
+            let now = Instant::now();
+            tokens.retain(|_, token| !token.is_expired(now));
+
+            // Only the unexpired tokens of this connection count towards its
+            // limit.
+            peer.mining_job_tokens
+                .retain(|token| tokens.contains_key(token));
+            if peer.mining_job_tokens.len() >= self.config.max_tokens_per_connection {
+                return Err(Error::RequirementError(
+                    "the connection cannot hold more than max_tokens_per_connection mining_job_tokens".into(),
+                ));
+            }
+
+            self.insert_token(
+                &mut tokens,
+                MiningJobTokenState::Allocated {
+                    async_mining_allowed,
+                },
+            )
+        };
+        peer.mining_job_tokens.insert(token.clone());
+
+        let success = AllocateMiningJobTokenSuccess::new(
+            msg.request_id,
+            token,
+            self.config.coinbase_output_max_additional_size,
+            self.config.coinbase_output.clone(),
+            async_mining_allowed,
+        )?;
+
+        peer.pending_msg_buffer
+            .lock()
+            .unwrap()
+            .push(frame(&success)?);
+
+        Ok(())
+    }
+
+    fn handle_commit_mining_job(&self, msg: CommitMiningJob, peer: &mut Peer<E>) -> Result<()> {
+        check_setup_conn(peer)?;
+
+        // A mining_job_token can only be committed on the connection it was
+        // allocated on.
+        let result = if peer.mining_job_tokens.contains(&msg.mining_job_token.data) {
+            self.commit_mining_job(&msg)
+        } else {
+            Err(CommitMiningJobErrorCode::InvalidMiningJobToken)
+        };
+
+        let response = match result {
+            Ok(token) => {
+                peer.mining_job_tokens.remove(&msg.mining_job_token.data);
+                peer.mining_job_tokens.insert(token.clone());
+                frame(&CommitMiningJobSuccess::new(msg.request_id, token)?)?
+            }
+            Err(error_code) => frame(&CommitMiningJobError::new(
+                msg.request_id,
+                error_code,
+                Vec::new(),
+            )?)?,
+        };
+
+        peer.pending_msg_buffer.lock().unwrap().push(response);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codec::unframe;
+    use crate::network::ConnectionEncryptor;
+    use crate::types::{MessageType, U256};
+    use bitcoin::{consensus::encode::serialize, Script};
+
+    fn make_output(value: u64, script_pubkey: &[u8]) -> Vec<u8> {
+        serialize(&TxOut {
+            value,
+            script_pubkey: Script::from(script_pubkey.to_vec()),
+        })
+    }
+
+    fn pool_output() -> Vec<u8> {
+        make_output(625000000, &[0xaa, 0xbb, 0xcc, 0xdd])
+    }
+
+    fn make_config(async_mining_allowed: bool) -> JobNegotiatorConfig {
+        JobNegotiatorConfig::new(pool_output(), 20, async_mining_allowed)
+    }
+
+    fn make_job_negotiator(async_mining_allowed: bool) -> JobNegotiator {
+        JobNegotiator::new(make_config(async_mining_allowed))
+    }
+
+    fn make_setup_conn(flags: SetupConnectionFlags) -> job_negotiation::SetupConnection {
+        job_negotiation::SetupConnection::new(
+            2,
+            2,
+            flags,
+            "0.0.0.0",
+            8545,
+            "Bitmain",
+            "S9i 13.5",
+            "braiins-os-2018-09-22-1-hash",
+            "some-device-uuid",
+        )
+        .unwrap()
+    }
+
+    fn make_commit_mining_job<T: Into<Vec<u8>>, U: Into<Vec<u8>>>(
+        token: T,
+        coinbase_tx_outputs: U,
+    ) -> CommitMiningJob {
+        CommitMiningJob::new(
+            1u32,
+            token,
+            2u32,
+            1u32,
+            [0u8; 2],
+            0xffffffffu32,
+            625000000u64,
+            coinbase_tx_outputs,
+            0u32,
+            4u16,
+            1u64,
+            Vec::new(),
+            U256([0u8; 32]),
+            Vec::new(),
+        )
+        .unwrap()
+    }
+
+    fn setup_peer(jn: &JobNegotiator) -> Peer<ConnectionEncryptor> {
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+        jn.handle_jn_new_conn(make_setup_conn(SetupConnectionFlags::empty()), &mut peer)
+            .unwrap();
+        peer.get_pending_msgs();
+        peer
+    }
+
+    fn allocate_token(jn: &JobNegotiator, peer: &mut Peer<ConnectionEncryptor>) -> Vec<u8> {
+        let msg = AllocateMiningJobToken::new("braiinstest", 1).unwrap();
+        jn.handle_allocate_mining_job_token(msg, peer).unwrap();
+
+        let msgs = peer.get_pending_msgs();
+        unframe::<AllocateMiningJobTokenSuccess>(&msgs[0])
+            .unwrap()
+            .mining_job_token
+            .into()
+    }
+
+    #[test]
+    fn new_conn_success() {
+        let jn = make_job_negotiator(true);
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+
+        jn.handle_jn_new_conn(
+            make_setup_conn(SetupConnectionFlags::REQUIRES_ASYNC_JOB_MINING),
+            &mut peer,
+        )
+        .unwrap();
+
+        let msgs = peer.get_pending_msgs();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].message_type, MessageType::SetupConnectionSuccess);
+        assert!(matches!(
+            peer.setup_conn_msg,
+            Some(SetupConnection::JobNegotiation(_))
+        ));
+    }
+
+    #[test]
+    fn new_conn_async_not_allowed() {
+        let jn = make_job_negotiator(false);
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+
+        jn.handle_jn_new_conn(
+            make_setup_conn(SetupConnectionFlags::REQUIRES_ASYNC_JOB_MINING),
+            &mut peer,
+        )
+        .unwrap();
+
+        let msgs = peer.get_pending_msgs();
+        let error = unframe::<SetupConnectionError>(&msgs[0]).unwrap();
+        assert_eq!(
+            error.error_code,
+            SetupConnectionErrorCode::UnsupportedFeatureFlags
+        );
+        assert_eq!(error.flags, SetupConnectionFlags::REQUIRES_ASYNC_JOB_MINING);
+        assert!(peer.setup_conn_msg.is_none());
+    }
+
+    #[test]
+    fn allocate_requires_setup_conn() {
+        let jn = make_job_negotiator(true);
+        let mut peer = Peer::new(ConnectionEncryptor::new_inbound(None));
+
+        let msg = AllocateMiningJobToken::new("braiinstest", 1).unwrap();
+        assert!(matches!(
+            jn.handle_allocate_mining_job_token(msg, &mut peer),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+
+    #[test]
+    fn allocate_token_state() {
+        let jn = make_job_negotiator(false);
+        let mut peer = setup_peer(&jn);
+
+        let token = allocate_token(&jn, &mut peer);
+        assert_eq!(
+            jn.token_state(&token),
+            Some(MiningJobTokenState::Allocated {
+                async_mining_allowed: false
+            })
+        );
+        assert!(!jn.is_token_usable(&token));
+
+        let jn = make_job_negotiator(true);
+        let mut peer = setup_peer(&jn);
+
+        let token = allocate_token(&jn, &mut peer);
+        assert!(jn.is_token_usable(&token));
+    }
+
+    #[test]
+    fn commit_mining_job_success() {
+        let jn = make_job_negotiator(false);
+        let mut peer = setup_peer(&jn);
+        let token = allocate_token(&jn, &mut peer);
+
+        let mut outputs = make_output(1, &[0x51]);
+        outputs.extend(pool_output());
+
+        jn.handle_commit_mining_job(make_commit_mining_job(token.clone(), outputs), &mut peer)
+            .unwrap();
+
+        let msgs = peer.get_pending_msgs();
+        let success = unframe::<CommitMiningJobSuccess>(&msgs[0]).unwrap();
+        let new_token: Vec<u8> = success.new_mining_job_token.into();
+
+        assert_eq!(jn.token_state(&token), None);
+        assert_eq!(
+            jn.token_state(&new_token),
+            Some(MiningJobTokenState::Committed)
+        );
+        assert!(jn.is_token_usable(&new_token));
+
+        // Neither the committed token nor the already committed allocated
+        // token can be used to commit another job.
+        for token in vec![new_token, token] {
+            jn.handle_commit_mining_job(make_commit_mining_job(token, pool_output()), &mut peer)
+                .unwrap();
+
+            let msgs = peer.get_pending_msgs();
+            let error = unframe::<CommitMiningJobError>(&msgs[0]).unwrap();
+            assert_eq!(
+                error.error_code,
+                CommitMiningJobErrorCode::InvalidMiningJobToken
+            );
+        }
+    }
+
+    #[test]
+    fn commit_mining_job_other_connection() {
+        let jn = make_job_negotiator(false);
+        let mut peer = setup_peer(&jn);
+        let token = allocate_token(&jn, &mut peer);
+
+        // The token was allocated on another connection.
+        let mut other_peer = setup_peer(&jn);
+        jn.handle_commit_mining_job(
+            make_commit_mining_job(token.clone(), pool_output()),
+            &mut other_peer,
+        )
+        .unwrap();
+
+        let msgs = other_peer.get_pending_msgs();
+        let error = unframe::<CommitMiningJobError>(&msgs[0]).unwrap();
+        assert_eq!(
+            error.error_code,
+            CommitMiningJobErrorCode::InvalidMiningJobToken
+        );
+        assert!(jn.token_state(&token).is_some());
+    }
+
+    #[test]
+    fn token_expiry() {
+        let mut config = make_config(true);
+        config.token_ttl = Duration::from_secs(0);
+        let jn = JobNegotiator::new(config);
+        let mut peer = setup_peer(&jn);
+
+        let token = allocate_token(&jn, &mut peer);
+        assert_eq!(jn.token_state(&token), None);
+        assert!(!jn.is_token_usable(&token));
+        assert_eq!(
+            jn.validate_commit_mining_job(&make_commit_mining_job(token, pool_output())),
+            Err(CommitMiningJobErrorCode::InvalidMiningJobToken)
+        );
+
+        jn.remove_expired_tokens();
+        assert!(jn.tokens.lock().unwrap().is_empty());
+
+        // Expired tokens don't count towards the limit of the connection.
+        for _ in 0..JobNegotiatorConfig::DEFAULT_MAX_TOKENS_PER_CONNECTION + 1 {
+            allocate_token(&jn, &mut peer);
+        }
+    }
+
+    #[test]
+    fn token_limit_per_connection() {
+        let mut config = make_config(false);
+        config.max_tokens_per_connection = 2;
+        let jn = JobNegotiator::new(config);
+        let mut peer = setup_peer(&jn);
+
+        let token = allocate_token(&jn, &mut peer);
+        allocate_token(&jn, &mut peer);
+
+        let msg = AllocateMiningJobToken::new("braiinstest", 1).unwrap();
+        assert!(matches!(
+            jn.handle_allocate_mining_job_token(msg.clone(), &mut peer),
+            Err(Error::RequirementError { .. })
+        ));
+
+        // The limit is per connection.
+        let mut other_peer = setup_peer(&jn);
+        allocate_token(&jn, &mut other_peer);
+
+        // Removed tokens no longer count towards the limit.
+        jn.remove_token(&token);
+        jn.handle_allocate_mining_job_token(msg, &mut peer).unwrap();
+    }
+
+    #[test]
+    fn commit_mining_job_policy_errors() {
+        let jn = make_job_negotiator(false);
+        let mut peer = setup_peer(&jn);
+        let token = allocate_token(&jn, &mut peer);
+
+        let outputs_err = |outputs: Vec<u8>| {
+            assert_eq!(
+                jn.validate_commit_mining_job(&make_commit_mining_job(token.clone(), outputs)),
+                Err(CommitMiningJobErrorCode::InvalidJobParamValueCoinbaseTxOutputs)
+            );
+        };
+
+        // Unknown token.
+        assert_eq!(
+            jn.validate_commit_mining_job(&make_commit_mining_job([0u8; 2], pool_output())),
+            Err(CommitMiningJobErrorCode::InvalidMiningJobToken)
+        );
+
+        // Missing the pool coinbase output.
+        outputs_err(make_output(1, &[0x51]));
+
+        // The pool coinbase output is only part of the script of an output.
+        outputs_err(make_output(1, &pool_output()));
+
+        // The outputs are not a sequence of whole outputs.
+        let mut outputs = pool_output();
+        outputs.push(0x00);
+        outputs_err(outputs);
+
+        // Exceeding the coinbase_output_max_additional_size.
+        let mut outputs = pool_output();
+        for _ in 0..3 {
+            outputs.extend(make_output(1, &[0x51]));
+        }
+        outputs_err(outputs);
+
+        // The rejected commits leave the token allocated.
+        assert_eq!(
+            jn.token_state(&token),
+            Some(MiningJobTokenState::Allocated {
+                async_mining_allowed: false
+            })
+        );
+    }
+}
@@ -16,6 +16,30 @@ pub trait NewConnReceiver<E: Encryptor> {
     ) -> Result<()>;
 }
 
+/// A trait that should be applied to upstream devices such as a Mining Pool
+/// Server that negotiate mining jobs with Job Negotiators over a
+/// [Job Negotiation connection](../job_negotiation/index.html). Responses are
+/// queued on the pending_msg_buffer of the Peer.
+pub trait JobNegotiationReceiver<E: Encryptor> {
+    fn handle_jn_new_conn(
+        &self,
+        new_conn: job_negotiation::SetupConnection,
+        peer: &mut Peer<E>,
+    ) -> Result<()>;
+
+    fn handle_allocate_mining_job_token(
+        &self,
+        msg: job_negotiation::AllocateMiningJobToken,
+        peer: &mut Peer<E>,
+    ) -> Result<()>;
+
+    fn handle_commit_mining_job(
+        &self,
+        msg: job_negotiation::CommitMiningJob,
+        peer: &mut Peer<E>,
+    ) -> Result<()>;
+}
+
 /// A trait that should be applied to downstream devices such as Mining Devices
 /// and proxies that can handle responses after attempting to open a New Mining
 /// Connection.
@@ -1,11 +1,19 @@
+mod capture;
 mod channel;
 mod config;
 mod encryptor;
+mod endpoint;
+mod job_negotiator;
 mod message_handler;
 mod peer;
 
-pub use channel::{new_channel_id, ChanID, Channel, ChannelManager};
-pub use config::{NetworkConfig, NoiseConfig, ServerConfig};
+pub use capture::{CaptureReader, CaptureRecord, CaptureWriter, Direction, Replayer, Timing};
+pub use channel::{new_channel_id, ChanID, Channel, ChannelManager, Extranonce};
+pub use config::{JobNegotiatorConfig, NetworkConfig, NoiseConfig, ServerConfig};
 pub use encryptor::{ConnectionEncryptor, Encryptor};
-pub use message_handler::{JobNegotiationInitiator, MiningInitiator, NewConnReceiver};
+pub use endpoint::Endpoint;
+pub use job_negotiator::{JobNegotiator, MiningJobTokenState};
+pub use message_handler::{
+    JobNegotiationInitiator, JobNegotiationReceiver, MiningInitiator, NewConnReceiver,
+};
 pub use peer::Peer;
@@ -1,5 +1,10 @@
-use crate::{codec::Message, common::SetupConnection, network::Encryptor};
-use std::{mem, sync::Mutex};
+use crate::{
+    codec::{deserialize_strict, Message},
+    common::SetupConnection,
+    error::{Error, Result},
+    network::{CaptureWriter, Direction, Encryptor},
+};
+use std::{collections::HashSet, io, mem, sync::Mutex};
 
 // TODO: Come up with a better name than Peer.
 /// Peer is not really a Peer since this is a Client-Server architecture but
@@ -23,6 +28,19 @@ pub struct Peer<E: Encryptor> {
     /// by message handlers receiving and processing a message and requiring
     /// to send a response.
     pub pending_msg_buffer: Mutex<Vec<Message>>,
+
+    /// An optional capture of the plaintext messages sent and received on
+    /// this connection, which can be replayed for regression testing. The
+    /// capture is disabled if recording a message fails.
+    pub capture: Mutex<Option<CaptureWriter<Box<dyn io::Write + Send>>>>,
+
+    /// The error that disabled the capture, if recording a message failed.
+    pub capture_error: Mutex<Option<Error>>,
+
+    /// The mining_job_tokens allocated to the counterparty on this Job
+    /// Negotiation connection, used to limit the number of tokens a single
+    /// connection can hold.
+    pub mining_job_tokens: HashSet<Vec<u8>>,
 }
 
 impl<E> Peer<E>
@@ -34,14 +52,76 @@ where
             encryptor,
             setup_conn_msg: None,
             pending_msg_buffer: Mutex::new(Vec::new()),
+            capture: Mutex::new(None),
+            capture_error: Mutex::new(None),
+            mining_job_tokens: HashSet::new(),
+        }
+    }
+
+    /// Starts capturing the messages on this connection to the writer,
+    /// replacing any previous capture.
+    pub fn start_capture<W: io::Write + Send + 'static>(&mut self, writer: W) -> Result<()> {
+        let writer: Box<dyn io::Write + Send> = Box::new(writer);
+        *self.capture.lock().unwrap() = Some(CaptureWriter::new(writer)?);
+        *self.capture_error.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Stops capturing the messages on this connection. The capture is flushed
+    /// and its CaptureWriter is returned, or None if the connection isn't
+    /// being captured.
+    pub fn stop_capture(&mut self) -> Result<Option<CaptureWriter<Box<dyn io::Write + Send>>>> {
+        match self.capture.lock().unwrap().take() {
+            Some(mut writer) => {
+                writer.flush()?;
+                Ok(Some(writer))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Takes the error that disabled the capture, if recording a message
+    /// failed since the capture was started.
+    pub fn take_capture_error(&self) -> Option<Error> {
+        self.capture_error.lock().unwrap().take()
+    }
+
+    /// Records the messages in the capture, if the connection is being
+    /// captured. A failure to record is not fatal to the connection, the
+    /// capture is disabled and the error is kept in capture_error.
+    fn record_msgs(&self, direction: Direction, messages: &[Message]) {
+        let mut capture = self.capture.lock().unwrap();
+
+        if let Some(writer) = capture.as_mut() {
+            if let Err(err) = messages
+                .iter()
+                .try_for_each(|message| writer.record(direction, message))
+            {
+                *capture = None;
+                *self.capture_error.lock().unwrap() = Some(err);
+            }
         }
     }
 
+    /// Deserializes a network frame received from the counterparty, once it
+    /// has been decrypted, into a Message. If the connection is being
+    /// captured, the Message is recorded as received.
+    pub fn recv_msg(&self, bytes: &[u8]) -> Result<Message> {
+        let message = deserialize_strict::<Message>(bytes)?;
+        self.record_msgs(Direction::Received, std::slice::from_ref(&message));
+
+        Ok(message)
+    }
+
     /// Drains the Messages from the pending_msg_buffer in order them to be sent
     /// over the wire. An empty buffer is left in it's place.
+    ///
+    /// If the connection is being captured, the Messages are recorded as sent.
     pub fn get_pending_msgs(&self) -> Vec<Message> {
         let mut result = Vec::new();
         mem::swap(&mut *self.pending_msg_buffer.lock().unwrap(), &mut result);
+        self.record_msgs(Direction::Sent, &result);
+
         result
     }
 }
@@ -26,10 +26,14 @@ pub type StaticPublicKey = noiseexplorer_nx::types::PublicKey;
 impl Serializable for StaticPublicKey {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let public_key = self.as_bytes();
-        writer.write(&public_key)?;
+        writer.write_all(&public_key)?;
 
         Ok(public_key.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        self.as_bytes().len()
+    }
 }
 
 /// StaticKeyPair is a Keypair used by the responder (Server) as a pre-determined
@@ -52,10 +56,14 @@ pub type Signature = ed25519_dalek::Signature;
 impl Serializable for Signature {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let public_key = self.to_bytes();
-        writer.write(&public_key)?;
+        writer.write_all(&public_key)?;
 
         Ok(public_key.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        ed25519_dalek::SIGNATURE_LENGTH
+    }
 }
 
 impl Deserializable for Signature {
@@ -0,0 +1,45 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// CoinbaseOutputDataSize is sent from the Client to the Template Provider
+    /// to indicate the maximum additional size of the coinbase transaction
+    /// outputs the Client will add. The Template Provider MUST reduce the
+    /// space available for transactions in subsequent templates accordingly.
+    CoinbaseOutputDataSize,
+
+    /// The maximum additional serialized bytes which the Client will add in
+    /// coinbase transaction outputs.
+    coinbase_output_max_additional_size u32
+);
+
+impl CoinbaseOutputDataSize {
+    pub fn new(coinbase_output_max_additional_size: u32) -> Result<CoinbaseOutputDataSize> {
+        Ok(CoinbaseOutputDataSize {
+            coinbase_output_max_additional_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_coinbase_output_data_size() -> CoinbaseOutputDataSize {
+        CoinbaseOutputDataSize::new(1u32).unwrap()
+    }
+
+    fn make_serialized_coinbase_output_data_size() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, // coinbase_output_max_additional_size
+        ];
+    }
+
+    impl_message_tests!(
+        CoinbaseOutputDataSize,
+        make_serialized_coinbase_output_data_size,
+        make_deserialized_coinbase_output_data_size
+    );
+}
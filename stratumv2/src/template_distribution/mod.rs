@@ -0,0 +1,33 @@
+//! The sub protocol allows a Template Provider (typically a Bitcoin node) to
+//! distribute block templates to a Mining Pool or Job Negotiator. The protocol
+//! is intended to replace getblocktemplate.
+//!
+//! The Client (Mining Pool or Job Negotiator) first sends a
+//! `CoinbaseOutputDataSize` message, the Template Provider then pushes
+//! `NewTemplate` and `SetNewPrevHash` messages as the chain tip and mempool
+//! change. The Client can request the transactions of a template and submit
+//! a block solution for a template.
+
+mod coinbase_output_data_size;
+mod new_template;
+mod request_transaction_data;
+mod request_transaction_data_error;
+mod request_transaction_data_success;
+mod set_new_prev_hash;
+mod setup_connection;
+mod setup_connection_error;
+mod setup_connection_success;
+mod submit_solution;
+
+pub use coinbase_output_data_size::CoinbaseOutputDataSize;
+pub use new_template::NewTemplate;
+pub use request_transaction_data::RequestTransactionData;
+pub use request_transaction_data_error::{
+    RequestTransactionDataError, RequestTransactionDataErrorCode,
+};
+pub use request_transaction_data_success::RequestTransactionDataSuccess;
+pub use set_new_prev_hash::SetNewPrevHash;
+pub use setup_connection::{SetupConnection, SetupConnectionFlags};
+pub use setup_connection_error::SetupConnectionError;
+pub use setup_connection_success::{SetupConnectionSuccess, SetupConnectionSuccessFlags};
+pub use submit_solution::SubmitSolution;
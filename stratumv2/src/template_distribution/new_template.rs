@@ -0,0 +1,132 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_255, B0_64K, SEQ0_255, U256};
+
+impl_message!(
+    /// NewTemplate is sent from the Template Provider to the Client to provide
+    /// a new block template. The Client uses the template to build mining jobs.
+    NewTemplate,
+
+    /// The Template Provider's identifier for the template. This MUST be
+    /// strictly increasing, the current UNIX time may be used in place of an
+    /// identifier.
+    template_id u64,
+
+    /// True if the template is intended for a future
+    /// [SetNewPrevHash](struct.SetNewPrevHash.html) message. If false, the
+    /// template relates to the last sent SetNewPrevHash message and work
+    /// should start on it immediately.
+    future_template bool,
+
+    /// A valid header version field that reflects the current network
+    /// consensus. The general purpose bits (as specified in BIP320) can be
+    /// freely manipulated by the downstream node.
+    version u32,
+
+    /// The coinbase transaction nVersion field.
+    coinbase_tx_version u32,
+
+    /// Up to 8 bytes (not including the length byte) which are to be placed at
+    /// the beginning of the coinbase field in the coinbase transaction.
+    coinbase_prefix B0_255,
+
+    /// The coinbase transaction input's nSequence field.
+    coinbase_tx_input_sequence u32,
+
+    /// The value, in satoshis, available for spending in coinbase outputs added
+    /// by the Client. Includes both transaction fees and block subsidy.
+    coinbase_tx_value_remaining u64,
+
+    /// The number of transaction outputs included in coinbase_tx_outputs.
+    coinbase_tx_outputs_count u32,
+
+    /// Bitcoin transaction outputs to be included as the last outputs in the
+    /// coinbase transaction.
+    coinbase_tx_outputs B0_64K,
+
+    /// The locktime field in the coinbase transaction.
+    coinbase_tx_locktime u32,
+
+    /// The merkle path hashes ordered from deepest.
+    merkle_path SEQ0_255<U256>
+);
+
+impl NewTemplate {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<T: Into<Vec<u8>>, U: Into<Vec<u8>>, V: Into<Vec<U256>>>(
+        template_id: u64,
+        future_template: bool,
+        version: u32,
+        coinbase_tx_version: u32,
+        coinbase_prefix: T,
+        coinbase_tx_input_sequence: u32,
+        coinbase_tx_value_remaining: u64,
+        coinbase_tx_outputs_count: u32,
+        coinbase_tx_outputs: U,
+        coinbase_tx_locktime: u32,
+        merkle_path: V,
+    ) -> Result<NewTemplate> {
+        Ok(NewTemplate {
+            template_id,
+            future_template,
+            version,
+            coinbase_tx_version,
+            coinbase_prefix: B0_255::new(coinbase_prefix)?,
+            coinbase_tx_input_sequence,
+            coinbase_tx_value_remaining,
+            coinbase_tx_outputs_count,
+            coinbase_tx_outputs: B0_64K::new(coinbase_tx_outputs)?,
+            coinbase_tx_locktime,
+            merkle_path: SEQ0_255::new(merkle_path)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_new_template() -> NewTemplate {
+        NewTemplate::new(
+            1u64,
+            true,
+            2u32,
+            3u32,
+            [4u8; 2],
+            5u32,
+            6u64,
+            7u32,
+            [8u8; 2],
+            9u32,
+            vec![U256([10u8; 32])],
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_new_template() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+            0x01, // future_template
+            0x02, 0x00, 0x00, 0x00, // version
+            0x03, 0x00, 0x00, 0x00, // coinbase_tx_version
+            0x02, 0x04, 0x04, // coinbase_prefix
+            0x05, 0x00, 0x00, 0x00, // coinbase_tx_input_sequence
+            0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // coinbase_tx_value_remaining
+            0x07, 0x00, 0x00, 0x00, // coinbase_tx_outputs_count
+            0x02, 0x00, 0x08, 0x08, // coinbase_tx_outputs
+            0x09, 0x00, 0x00, 0x00, // coinbase_tx_locktime
+            0x01, // merkle_path length
+            0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a,
+            0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a, 0x0a,
+            0x0a, 0x0a, 0x0a, 0x0a, // merkle_path[0]
+        ];
+    }
+
+    impl_message_tests!(
+        NewTemplate,
+        make_serialized_new_template,
+        make_deserialized_new_template
+    );
+}
@@ -0,0 +1,41 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::MessageType;
+
+impl_message!(
+    /// RequestTransactionData is sent from the Client to the Template Provider
+    /// to request the full transaction data of a template. This is typically
+    /// used by a Job Negotiator to negotiate the template with a Mining Pool.
+    RequestTransactionData,
+
+    /// The template_id of the template to request the transaction data for.
+    template_id u64
+);
+
+impl RequestTransactionData {
+    pub fn new(template_id: u64) -> Result<RequestTransactionData> {
+        Ok(RequestTransactionData { template_id })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_request_transaction_data() -> RequestTransactionData {
+        RequestTransactionData::new(1u64).unwrap()
+    }
+
+    fn make_serialized_request_transaction_data() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+        ];
+    }
+
+    impl_message_tests!(
+        RequestTransactionData,
+        make_serialized_request_transaction_data,
+        make_deserialized_request_transaction_data
+    );
+}
@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::impl_error_code_enum;
+use crate::impl_message;
+use crate::types::MessageType;
+
+/// Contains the error codes for the [RequestTransactionDataError](struct.RequestTransactionDataError.html)
+/// message. Each error code has a default STR0_255 message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestTransactionDataErrorCode {
+    /// The template_id is not known to the Template Provider.
+    TemplateIdNotFound,
+
+    /// The template_id is known but the template is stale.
+    StaleTemplateId,
+}
+
+impl_error_code_enum!(
+    RequestTransactionDataErrorCode,
+    RequestTransactionDataErrorCode::TemplateIdNotFound => "template-id-not-found",
+    RequestTransactionDataErrorCode::StaleTemplateId => "stale-template-id"
+);
+
+impl_message!(
+    /// RequestTransactionDataError is sent from the Template Provider to the
+    /// Client when the transaction data of a template can not be provided.
+    RequestTransactionDataError,
+
+    /// The template_id received in the RequestTransactionData message.
+    template_id u64,
+
+    /// Pre-determined human readable error codes for the RequestTransactionData message.
+    error_code RequestTransactionDataErrorCode
+);
+
+impl RequestTransactionDataError {
+    pub fn new(
+        template_id: u64,
+        error_code: RequestTransactionDataErrorCode,
+    ) -> Result<RequestTransactionDataError> {
+        Ok(RequestTransactionDataError {
+            template_id,
+            error_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_request_transaction_data_error() -> RequestTransactionDataError {
+        RequestTransactionDataError::new(1u64, RequestTransactionDataErrorCode::StaleTemplateId)
+            .unwrap()
+    }
+
+    fn make_serialized_request_transaction_data_error() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+            0x11, 0x73, 0x74, 0x61, 0x6c, 0x65, 0x2d, 0x74, 0x65, 0x6d, 0x70, 0x6c, 0x61, 0x74,
+            0x65, 0x2d, 0x69, 0x64, // error_code
+        ];
+    }
+
+    impl_message_tests!(
+        RequestTransactionDataError,
+        make_serialized_request_transaction_data_error,
+        make_deserialized_request_transaction_data_error
+    );
+}
@@ -0,0 +1,69 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_16M, B0_64K, SEQ0_64K};
+
+impl_message!(
+    /// RequestTransactionDataSuccess is sent from the Template Provider to the
+    /// Client in response to a [RequestTransactionData](struct.RequestTransactionData.html)
+    /// message.
+    RequestTransactionDataSuccess,
+
+    /// The template_id of the template that the transaction data belongs to.
+    template_id u64,
+
+    /// Extra data which the Pool may require to validate the work.
+    excess_data B0_64K,
+
+    /// The full serialized transactions in the template, excluding the
+    /// coinbase transaction.
+    transaction_list SEQ0_64K<B0_16M>
+);
+
+impl RequestTransactionDataSuccess {
+    pub fn new<T: Into<Vec<u8>>, U: Into<Vec<B0_16M>>>(
+        template_id: u64,
+        excess_data: T,
+        transaction_list: U,
+    ) -> Result<RequestTransactionDataSuccess> {
+        Ok(RequestTransactionDataSuccess {
+            template_id,
+            excess_data: B0_64K::new(excess_data)?,
+            transaction_list: SEQ0_64K::new(transaction_list)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_request_transaction_data_success() -> RequestTransactionDataSuccess {
+        RequestTransactionDataSuccess::new(
+            1u64,
+            [2u8; 2],
+            vec![
+                B0_16M::new([3u8; 2]).unwrap(),
+                B0_16M::new([4u8; 1]).unwrap(),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_request_transaction_data_success() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+            0x02, 0x00, 0x02, 0x02, // excess_data
+            0x02, 0x00, // transaction_list length
+            0x02, 0x00, 0x00, 0x03, 0x03, // transaction_list[0]
+            0x01, 0x00, 0x00, 0x04, // transaction_list[1]
+        ];
+    }
+
+    impl_message_tests!(
+        RequestTransactionDataSuccess,
+        make_serialized_request_transaction_data_success,
+        make_deserialized_request_transaction_data_success
+    );
+}
@@ -0,0 +1,80 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::types::{MessageType, U256};
+
+impl_message!(
+    /// SetNewPrevHash is sent from the Template Provider to the Client when a
+    /// new block is found on the network. The message activates the future
+    /// template identified by template_id and all other templates become
+    /// invalid.
+    ///
+    /// This message shares its name with the
+    /// [Mining Protocol SetNewPrevHash](../mining/struct.SetNewPrevHash.html)
+    /// but has a different message type and fields.
+    SetNewPrevHash => MessageType::TemplateDistributionSetNewPrevHash,
+
+    /// The identifier of the future template to be activated.
+    template_id u64,
+
+    /// The previous block hash to be used in the block header.
+    prev_hash U256,
+
+    /// The nTime field in the block header at which the client should start
+    /// (usually current time). This is NOT the minimum valid nTime value.
+    header_timestamp u32,
+
+    /// The nBits field of the block header.
+    n_bits u32,
+
+    /// The maximum double-SHA256 hash value which would represent a valid
+    /// block. This may be lower than the target implied by nBits.
+    target U256
+);
+
+impl SetNewPrevHash {
+    pub fn new<T: Into<U256>, U: Into<U256>>(
+        template_id: u64,
+        prev_hash: T,
+        header_timestamp: u32,
+        n_bits: u32,
+        target: U,
+    ) -> Result<SetNewPrevHash> {
+        Ok(SetNewPrevHash {
+            template_id,
+            prev_hash: prev_hash.into(),
+            header_timestamp,
+            n_bits,
+            target: target.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_set_new_prev_hash() -> SetNewPrevHash {
+        SetNewPrevHash::new(1u64, [2u8; 32], 3u32, 4u32, [5u8; 32]).unwrap()
+    }
+
+    fn make_serialized_set_new_prev_hash() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+            0x02, 0x02, 0x02, 0x02, // prev_hash
+            0x03, 0x00, 0x00, 0x00, // header_timestamp
+            0x04, 0x00, 0x00, 0x00, // n_bits
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05, 0x05,
+            0x05, 0x05, 0x05, 0x05, // target
+        ];
+    }
+
+    impl_message_tests!(
+        SetNewPrevHash => MessageType::TemplateDistributionSetNewPrevHash,
+        make_serialized_set_new_prev_hash,
+        make_deserialized_set_new_prev_hash
+    );
+}
@@ -0,0 +1,43 @@
+use crate::{impl_bitflags_serde, impl_setup_connection};
+
+bitflags!(
+  /// Feature flags that can be passed to a SetupConnection message for the
+  /// template distribution protocol. The specification does not define any
+  /// flags for this protocol.
+  pub struct SetupConnectionFlags: u32 {
+    const NONE = 0;
+  }
+);
+
+impl_bitflags_serde!(SetupConnectionFlags);
+impl_setup_connection!(SetupConnectionFlags);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{deserialize, serialize};
+    use crate::impl_setup_connection_tests;
+
+    #[test]
+    fn flags_serialize() {
+        assert_eq!(
+            serialize(&SetupConnectionFlags::NONE).unwrap(),
+            0x00u32.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn flags_deserialize() {
+        assert_eq!(
+            deserialize::<SetupConnectionFlags>(&0x00u32.to_le_bytes()).unwrap(),
+            SetupConnectionFlags::NONE,
+        );
+
+        assert!(matches!(
+            deserialize::<SetupConnectionFlags>(&0x01u32.to_le_bytes()),
+            Err(Error::UnknownFlags { .. })
+        ));
+    }
+
+    impl_setup_connection_tests!(SetupConnectionFlags);
+}
@@ -0,0 +1,46 @@
+use crate::impl_setup_connection_error;
+use crate::template_distribution::SetupConnectionFlags;
+
+impl_setup_connection_error!(SetupConnectionFlags);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::impl_message_tests;
+
+    // The template distribution protocol has no feature flags, so the
+    // UnsupportedFeatureFlags error code used by impl_setup_connection_error_tests
+    // can never be returned.
+    fn make_deserialized_setup_connection_error() -> SetupConnectionError {
+        SetupConnectionError::new(
+            SetupConnectionFlags::NONE,
+            SetupConnectionErrorCode::UnsupportedProtocol,
+        )
+        .unwrap()
+    }
+
+    fn make_serialized_setup_connection_error() -> Vec<u8> {
+        return vec![
+            0x00, 0x00, 0x00, 0x00, // flags
+            0x14, 0x75, 0x6e, 0x73, 0x75, 0x70, 0x70, 0x6f, 0x72, 0x74, 0x65, 0x64, 0x2d, 0x70,
+            0x72, 0x6f, 0x74, 0x6f, 0x63, 0x6f, 0x6c, // error_code
+        ];
+    }
+
+    impl_message_tests!(
+        SetupConnectionError,
+        make_serialized_setup_connection_error,
+        make_deserialized_setup_connection_error
+    );
+
+    #[test]
+    fn empty_feature_flags_error() {
+        assert!(matches!(
+            SetupConnectionError::new(
+                SetupConnectionFlags::NONE,
+                SetupConnectionErrorCode::UnsupportedFeatureFlags
+            ),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::error::Result;
+use crate::impl_message;
+use crate::prelude::*;
+use crate::types::{MessageType, B0_64K};
+
+impl_message!(
+    /// SubmitSolution is sent from the Client to the Template Provider when a
+    /// share meeting the network target is found for a template. The Template
+    /// Provider builds the full block and propagates it to the network.
+    SubmitSolution,
+
+    /// The template_id of the template the solution was found for.
+    template_id u64,
+
+    /// The version field in the block header.
+    version u32,
+
+    /// The nTime field in the block header.
+    header_timestamp u32,
+
+    /// The nonce field in the block header.
+    header_nonce u32,
+
+    /// The full serialized coinbase transaction, meeting all the requirements
+    /// of the NewTemplate message.
+    coinbase_tx B0_64K
+);
+
+impl SubmitSolution {
+    pub fn new<T: Into<Vec<u8>>>(
+        template_id: u64,
+        version: u32,
+        header_timestamp: u32,
+        header_nonce: u32,
+        coinbase_tx: T,
+    ) -> Result<SubmitSolution> {
+        Ok(SubmitSolution {
+            template_id,
+            version,
+            header_timestamp,
+            header_nonce,
+            coinbase_tx: B0_64K::new(coinbase_tx)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::impl_message_tests;
+
+    fn make_deserialized_submit_solution() -> SubmitSolution {
+        SubmitSolution::new(1u64, 2u32, 3u32, 4u32, [5u8; 2]).unwrap()
+    }
+
+    fn make_serialized_submit_solution() -> Vec<u8> {
+        return vec![
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // template_id
+            0x02, 0x00, 0x00, 0x00, // version
+            0x03, 0x00, 0x00, 0x00, // header_timestamp
+            0x04, 0x00, 0x00, 0x00, // header_nonce
+            0x02, 0x00, 0x05, 0x05, // coinbase_tx
+        ];
+    }
+
+    impl_message_tests!(
+        SubmitSolution,
+        make_serialized_submit_solution,
+        make_deserialized_submit_solution
+    );
+}
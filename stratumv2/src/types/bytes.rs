@@ -1,7 +1,7 @@
-use crate::codec::{ByteParser, Deserializable, Serializable};
+use crate::codec::{io, ByteParser, Deserializable, Serializable};
 use crate::error::{Error, Result};
+use crate::prelude::*;
 use crate::types::U24;
-use std::io;
 
 /// An internal macro that implements a B0 type that is restricted according to a MAX_LENGTH.
 macro_rules! impl_sized_B0 {
@@ -29,12 +29,29 @@ macro_rules! impl_sized_B0 {
                     ));
                 }
 
-                use std::convert::TryInto;
+                use core::convert::TryInto;
                 Ok($type {
                     length: value.len().try_into().unwrap(),
                     data: value,
                 })
             }
+
+            /// Deserializes the B0 type from a ByteParser without copying the byte
+            /// buffer. The returned slice borrows from the bytes of the ByteParser.
+            pub fn deserialize_ref<'a>(parser: &mut ByteParser<'a>) -> Result<&'a [u8]> {
+                // Parse the length header before the buffer.
+                let header_length = $length_type::deserialize(parser)?;
+                // Then parse the byte buffer.
+                let bytes = parser.next_by(header_length.into())?;
+
+                if bytes.len() > Self::MAX_LENGTH {
+                    return Err(Error::RequirementError(
+                        "bytes size cannot be greater than MAX_LENGTH".into(),
+                    ));
+                }
+
+                Ok(bytes)
+            }
         }
 
         /// PartialEq implementation allowing direct comparison between the B0 type and Vec<u8>.
@@ -61,12 +78,28 @@ macro_rules! impl_sized_B0 {
         /// Deserialize trait implementation that allows a B0 to be deserialized from a ByteParser.
         impl Deserializable for $type {
             fn deserialize(parser: &mut ByteParser) -> Result<$type> {
-                // Parse the length header before the buffer.
-                let header_length = $length_type::deserialize(parser)?;
-                // Then parse the byte buffer.
-                let bytes = parser.next_by(header_length.clone().into())?;
+                $type::new(Self::deserialize_ref(parser)?)
+            }
+        }
 
-                $type::new(bytes)
+        /// The serde representation of a B0 is a hex string.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                crate::types::hex::serialize(&self.data, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<$type, D::Error> {
+                $type::new(crate::types::hex::deserialize(deserializer)?)
+                    .map_err(serde::de::Error::custom)
             }
         }
 
@@ -76,10 +109,14 @@ macro_rules! impl_sized_B0 {
                 // Write the length header.
                 let header_length = self.length.serialize(writer)?;
                 // Then write the byte buffer.
-                writer.write(self.data.as_slice())?;
+                writer.write_all(self.data.as_slice())?;
 
                 Ok(header_length + self.data.len())
             }
+
+            fn encoded_len(&self) -> usize {
+                self.length.encoded_len() + self.data.len()
+            }
         }
     };
 }
@@ -91,7 +128,7 @@ macro_rules! impl_sized_B0_tests {
     };
     ($type:ident, $length_type:ident, $max_length:expr) => {
         fn make_encoded_bytes(payload: &[u8]) -> Vec<u8> {
-            use std::convert::TryInto;
+            use core::convert::TryInto;
 
             let length = payload.len().try_into().unwrap();
             let header = serialize::<$length_type>(&length).unwrap();
@@ -103,7 +140,7 @@ macro_rules! impl_sized_B0_tests {
         }
 
         fn make_decoded_bytes(s: &[u8]) -> $type {
-            use std::convert::TryInto;
+            use core::convert::TryInto;
             $type {
                 length: s.len().try_into().unwrap(),
                 data: s.into(),
@@ -112,7 +149,7 @@ macro_rules! impl_sized_B0_tests {
 
         #[test]
         fn new_empty() {
-            use std::convert::TryInto;
+            use core::convert::TryInto;
             let data = vec![];
             let length: $length_type = 0usize.try_into().unwrap();
             assert_eq!(
@@ -126,7 +163,7 @@ macro_rules! impl_sized_B0_tests {
 
         #[test]
         fn new_nonempty() {
-            use std::convert::TryInto;
+            use core::convert::TryInto;
             let data = vec![1, 2, 3, 4, 5];
             let length: $length_type = 5usize.try_into().unwrap();
             assert_eq!(
@@ -143,7 +180,7 @@ macro_rules! impl_sized_B0_tests {
             // There's no need to test the larger variants of this macro. If it works for the
             // smaller sizes it will work for the larger ones as well.
             if $max_length < 100000 {
-                use std::convert::TryInto;
+                use core::convert::TryInto;
                 let data: Vec<u8> = [0; $max_length].into();
                 let length: $length_type = $max_length.try_into().unwrap();
                 assert_eq!(
@@ -183,6 +220,34 @@ macro_rules! impl_sized_B0_tests {
             let decoded = make_decoded_bytes(&[1, 2, 3, 4, 5]);
             assert_eq!(deserialize::<$type>(&encoded).unwrap(), decoded);
             assert_eq!(serialize(&decoded).unwrap(), encoded);
+            assert_eq!(decoded.encoded_len(), encoded.len());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn json_ok() {
+            let decoded = make_decoded_bytes(&[1, 2, 3, 0xff]);
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), "\"010203ff\"");
+            assert_eq!(
+                serde_json::from_str::<$type>("\"010203ff\"").unwrap(),
+                decoded
+            );
+            assert!(serde_json::from_str::<$type>("\"0g\"").is_err());
+
+            if $max_length < 100000 {
+                let over_limit = format!("\"{}\"", "00".repeat($max_length + 1));
+                assert!(serde_json::from_str::<$type>(&over_limit).is_err());
+            }
+        }
+
+        #[test]
+        fn deserialize_ref_ok() {
+            let encoded = make_encoded_bytes(&[1, 2, 3, 4, 5]);
+            let mut parser = ByteParser::new(&encoded, 0);
+            assert_eq!(
+                $type::deserialize_ref(&mut parser).unwrap(),
+                &[1, 2, 3, 4, 5]
+            );
         }
 
         #[test]
@@ -190,17 +255,17 @@ macro_rules! impl_sized_B0_tests {
             // No data to deserialize.
             assert!(matches!(
                 deserialize::<$type>(&[]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
             // No data after promised length.
             assert!(matches!(
                 deserialize::<$type>(&[1u8]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
             // Insufficient data after promised length.
             assert!(matches!(
                 deserialize::<$type>(&[2u8, 42u8]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
         }
     };
@@ -1,11 +1,11 @@
 pub mod macro_prelude {
-    pub use crate::codec::{ByteParser, Deserializable, Serializable};
+    pub use crate::codec::{io, ByteParser, Deserializable, Serializable};
     pub use crate::error::{Error, Result};
+    pub use crate::prelude::*;
     pub use crate::types::STR0_255;
-    pub use std::convert::TryFrom;
-    pub use std::fmt;
-    pub use std::io;
-    pub use std::str::FromStr;
+    pub use core::convert::TryFrom;
+    pub use core::fmt;
+    pub use core::str::FromStr;
 }
 
 /// Implemenation of all the common traits for ErrorCode enums.
@@ -72,6 +72,13 @@ macro_rules! impl_error_code_enum {
             fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
                 Ok(STR0_255::from(self).serialize(writer)?)
             }
+
+            fn encoded_len(&self) -> usize {
+                // The STR0_255 length header followed by the error code string.
+                1 + match self {
+                    $($variant => $str.len()),*
+                }
+            }
         }
 
         impl Deserializable for $name {
@@ -81,5 +88,31 @@ macro_rules! impl_error_code_enum {
                 $name::try_from(error_code)
             }
         }
+
+        /// The serde representation of the error code is its spec string.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(match self {
+                    $($variant => $str),*
+                })
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<$name, D::Error> {
+                let error_code = <String as serde::Deserialize>::deserialize(deserializer)?;
+
+                $name::from_str(&error_code).map_err(|_| {
+                    serde::de::Error::unknown_variant(&error_code, &[$($str),*])
+                })
+            }
+        }
     };
 }
@@ -1,7 +1,6 @@
-use crate::codec::{ByteParser, Deserializable, Serializable};
+use crate::codec::{io, ByteParser, Deserializable, Serializable};
 use crate::error::{Error, Result};
-use std::convert::{TryFrom, TryInto};
-use std::io;
+use core::convert::{TryFrom, TryInto};
 
 /// U24 is an unsigned integer type of 24-bits in little endian. This will
 /// usually be used to represent the length of a variable-length string or
@@ -75,11 +74,36 @@ impl Deserializable for U24 {
     }
 }
 
+/// The serde representation of a U24 is an integer.
+#[cfg(feature = "serde")]
+impl serde::Serialize for U24 {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for U24 {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<U24, D::Error> {
+        U24::new(<u32 as serde::Deserialize>::deserialize(deserializer)?)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl Serializable for U24 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
-        writer.write(&self.0.to_le_bytes()[0..3])?;
+        writer.write_all(&self.0.to_le_bytes()[0..3])?;
         Ok(3)
     }
+
+    fn encoded_len(&self) -> usize {
+        3
+    }
 }
 
 /// U256 is an unsigned integer type of 256-bits in little endian. This will
@@ -95,11 +119,35 @@ impl Deserializable for U256 {
     }
 }
 
+/// The serde representation of a U256 is a hex string of its bytes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for U256 {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        crate::types::hex::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for U256 {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<U256, D::Error> {
+        Ok(U256(crate::types::hex::deserialize_array(deserializer)?))
+    }
+}
+
 impl Serializable for U256 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
-        writer.write(&self.0)?;
+        writer.write_all(&self.0)?;
         Ok(32)
     }
+
+    fn encoded_len(&self) -> usize {
+        32
+    }
 }
 
 impl PartialEq<[u8; 32]> for U256 {
@@ -126,6 +174,77 @@ impl From<[u8; 32]> for U256 {
     }
 }
 
+/// ShortTxId is a 6 byte short transaction identifier, as specified in BIP152.
+/// This is used to identify transactions in a block template without sending
+/// the full transaction id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortTxId(pub [u8; 6]);
+
+impl Deserializable for ShortTxId {
+    fn deserialize(parser: &mut ByteParser) -> Result<ShortTxId> {
+        let mut buffer: [u8; 6] = [0; 6];
+        buffer[..].clone_from_slice(parser.next_by(6)?);
+        Ok(ShortTxId(buffer))
+    }
+}
+
+/// The serde representation of a ShortTxId is a hex string of its bytes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ShortTxId {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        crate::types::hex::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ShortTxId {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<ShortTxId, D::Error> {
+        Ok(ShortTxId(crate::types::hex::deserialize_array(
+            deserializer,
+        )?))
+    }
+}
+
+impl Serializable for ShortTxId {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_all(&self.0)?;
+        Ok(6)
+    }
+
+    fn encoded_len(&self) -> usize {
+        6
+    }
+}
+
+impl PartialEq<[u8; 6]> for ShortTxId {
+    fn eq(&self, other: &[u8; 6]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<ShortTxId> for [u8; 6] {
+    fn eq(&self, other: &ShortTxId) -> bool {
+        *self == other.0
+    }
+}
+
+impl From<ShortTxId> for [u8; 6] {
+    fn from(s: ShortTxId) -> Self {
+        s.0
+    }
+}
+
+impl From<[u8; 6]> for ShortTxId {
+    fn from(a: [u8; 6]) -> Self {
+        ShortTxId(a)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +276,15 @@ mod tests {
 
         assert_eq!(deserialize::<U24>(&encoded).unwrap(), decoded);
         assert_eq!(serialize(&decoded).unwrap(), encoded);
+        assert_eq!(decoded.encoded_len(), encoded.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn u24_json() {
+        assert_eq!(serde_json::to_string(&U24(5)).unwrap(), "5");
+        assert_eq!(serde_json::from_str::<U24>("5").unwrap(), U24(5));
+        assert!(serde_json::from_str::<U24>("16777216").is_err());
     }
 
     #[test]
@@ -164,7 +292,7 @@ mod tests {
         let encoded: [u8; 2] = [0; 2];
         assert!(matches!(
             deserialize::<U24>(&encoded),
-            Err(Error::ParseError { .. })
+            Err(Error::OutOfBounds { .. })
         ));
     }
 
@@ -196,6 +324,22 @@ mod tests {
         ]);
         assert_eq!(deserialize::<U256>(&encoded).unwrap(), decoded);
         assert_eq!(serialize(&decoded).unwrap(), encoded);
+        assert_eq!(decoded.encoded_len(), encoded.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn u256_json() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0x01;
+        bytes[31] = 0xff;
+
+        let json = format!("\"01{}ff\"", "00".repeat(30));
+        assert_eq!(serde_json::to_string(&U256(bytes)).unwrap(), json);
+        assert_eq!(serde_json::from_str::<U256>(&json).unwrap(), U256(bytes));
+
+        // The hex string must contain exactly 32 bytes.
+        assert!(serde_json::from_str::<U256>("\"0102\"").is_err());
     }
 
     #[test]
@@ -203,7 +347,34 @@ mod tests {
         let encoded: [u8; 31] = [0; 31];
         assert!(matches!(
             deserialize::<U256>(&encoded),
-            Err(Error::ParseError { .. })
+            Err(Error::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn short_tx_id_serde_ok() {
+        let encoded = vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let decoded = ShortTxId([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(deserialize::<ShortTxId>(&encoded).unwrap(), decoded);
+        assert_eq!(serialize(&decoded).unwrap(), encoded);
+        assert_eq!(decoded.encoded_len(), encoded.len());
+    }
+
+    #[test]
+    fn short_tx_id_serialize_err() {
+        // A short write is an error.
+        let mut buffer = [0u8; 5];
+        assert!(ShortTxId([0x01; 6])
+            .serialize(&mut &mut buffer[..])
+            .is_err());
+    }
+
+    #[test]
+    fn short_tx_id_deserialize_err() {
+        let encoded: [u8; 5] = [0; 5];
+        assert!(matches!(
+            deserialize::<ShortTxId>(&encoded),
+            Err(Error::OutOfBounds { .. })
         ));
     }
 }
@@ -4,20 +4,28 @@ pub mod macro_prelude {
 }
 
 /// Implemenation of all the ser/de traits for bitflags.
+///
+/// The names of the flags are listed after the name of the bitflags type to
+/// represent the flags as a list of flag names when the serde feature is
+/// enabled. Flags without a set bit, such as `NONE`, are not listed.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_bitflags_serde {
-    ($name:ident) => {
-        impl_bitflags_serde!($name, u32);
+    ($name:ident $(, $flag:ident)*) => {
+        impl_bitflags_serde!($name: u32 $(, $flag)*);
     };
 
-    ($name:ident, $underlying:ident) => {
+    ($name:ident: $underlying:ident $(, $flag:ident)*) => {
         use crate::types::flags::macro_prelude::*;
 
         impl Serializable for $name {
             fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
                 Ok(self.bits().serialize(writer)?)
             }
+
+            fn encoded_len(&self) -> usize {
+                self.bits().encoded_len()
+            }
         }
 
         impl Deserializable for $name {
@@ -25,5 +33,48 @@ macro_rules! impl_bitflags_serde {
                 $name::from_bits($underlying::deserialize(parser)?).ok_or(Error::UnknownFlags())
             }
         }
+
+        /// The serde representation of the flags is the list of the names of
+        /// the set flags.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                #[allow(unused_mut)]
+                let mut names: alloc::vec::Vec<&str> = alloc::vec::Vec::new();
+                $(
+                    if self.contains($name::$flag) {
+                        names.push(stringify!($flag));
+                    }
+                )*
+
+                serializer.collect_seq(names)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<$name, D::Error> {
+                #[allow(unused_mut)]
+                let mut flags = $name::empty();
+                for name in <alloc::vec::Vec<alloc::string::String> as serde::Deserialize>::deserialize(deserializer)? {
+                    match name.as_str() {
+                        $(stringify!($flag) => flags.insert($name::$flag),)*
+                        _ => {
+                            return Err(serde::de::Error::unknown_variant(
+                                &name,
+                                &[$(stringify!($flag)),*],
+                            ))
+                        }
+                    }
+                }
+
+                Ok(flags)
+            }
+        }
     };
 }
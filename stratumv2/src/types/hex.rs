@@ -0,0 +1,42 @@
+//! Helpers to represent byte types as hex strings in serde formats.
+
+use crate::prelude::*;
+use core::convert::TryInto;
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+/// Serializes the bytes as a lowercase hex string.
+pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    serializer.serialize_str(&hex)
+}
+
+/// Deserializes the bytes from a hex string.
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    if hex.len() % 2 != 0 {
+        return Err(de::Error::custom("odd number of hex digits"));
+    }
+
+    let digit = |c: u8| {
+        (c as char)
+            .to_digit(16)
+            .ok_or_else(|| de::Error::custom(format!("invalid hex character {:?}", c as char)))
+    };
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8))
+        .collect()
+}
+
+/// Deserializes a fixed size array of bytes from a hex string.
+pub(crate) fn deserialize_array<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    let bytes = deserialize(deserializer)?;
+    let length = bytes.len();
+
+    bytes
+        .try_into()
+        .map_err(|_| de::Error::invalid_length(length, &"the number of bytes of the type"))
+}
@@ -3,6 +3,7 @@ use crate::error::{Error, Result};
 /// MessageType contains all the variations for the byte representation of
 /// messages used in message frames.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageType {
     // Common messages
     SetupConnection,
@@ -33,16 +34,54 @@ pub enum MessageType {
     SetCustomMiningJobError,
     Reconnect,
     SetGroupChannel,
-    // TODO(chpatton013): Job negotiation protocol messages
-    // TODO(chpatton013): Template distribution protocol messages
+    // Job negotiation protocol messages
+    AllocateMiningJobToken,
+    AllocateMiningJobTokenSuccess,
+    IdentifyTransactions,
+    IdentifyTransactionsSuccess,
+    ProvideMissingTransactions,
+    ProvideMissingTransactionsSuccess,
+    CommitMiningJob,
+    CommitMiningJobSuccess,
+    CommitMiningJobError,
+    // Template distribution protocol messages
+    CoinbaseOutputDataSize,
+    NewTemplate,
+    TemplateDistributionSetNewPrevHash,
+    RequestTransactionData,
+    RequestTransactionDataSuccess,
+    RequestTransactionDataError,
+    SubmitSolution,
     // Testing messages
     TestMessage1,
     TestMessage2,
+    // Extension messages
+    /// A message of a protocol extension, identified by a non-zero
+    /// extension_type. Extension messages are not interpreted by the library,
+    /// allowing unknown extension frames to be passed through opaquely and
+    /// applications to define the messages of their own extensions.
+    ///
+    /// Extension message types should be created using
+    /// [MessageType::new_extension](enum.MessageType.html#method.new_extension),
+    /// which verifies the extension_type. Messages with an invalid
+    /// extension_type fail to serialize.
+    Extension {
+        ext_type: u16,
+        msg_type: u8,
+        channel_bit: bool,
+    },
 }
 
+/// The maximum value of an extension_type, the MSB of the extension_type in a
+/// message frame is used as the channel bit.
+const MAX_EXTENSION_TYPE: u16 = 0x7fff;
+
 macro_rules! impl_enum_message_types {
     ($type:ident, $($variant:path => ($ext_type:expr, $msg_type:expr, $channel_bit:expr)),*) => {
         impl $type {
+            /// Returns the MessageType of a message defined by the
+            /// specification. Extension messages are created using
+            /// [new_extension](#method.new_extension).
             pub fn new(ext_type: u16, msg_type: u8) -> Result<$type> {
                 match (ext_type, msg_type) {
                     $(($ext_type, $msg_type) => Ok($variant)),*,
@@ -50,23 +89,63 @@ macro_rules! impl_enum_message_types {
                 }
             }
 
-            pub fn ext_type(&self) -> u16 {
+            /// Returns the MessageType of a message of a protocol extension.
+            /// The extension_type must be non-zero, since zero is reserved
+            /// for the messages defined by the specification, and must fit in
+            /// 15 bits.
+            pub fn new_extension(ext_type: u16, msg_type: u8, channel_bit: bool) -> Result<$type> {
+                let message_type = $type::Extension {
+                    ext_type,
+                    msg_type,
+                    channel_bit,
+                };
+                message_type.verify()?;
+
+                Ok(message_type)
+            }
+
+            /// Verifies the extension_type of an extension message type. Since
+            /// the fields of the Extension variant are public, it can be
+            /// constructed with an extension_type that can't be framed.
+            pub fn verify(&self) -> Result<()> {
+                match self {
+                    $type::Extension { ext_type, .. }
+                        if *ext_type == 0 || *ext_type > MAX_EXTENSION_TYPE =>
+                    {
+                        Err(Error::RequirementError(format!(
+                            "extension_type must be between 1 and {}",
+                            MAX_EXTENSION_TYPE
+                        )))
+                    }
+                    _ => Ok(()),
+                }
+            }
+
+            pub const fn ext_type(&self) -> u16 {
                 match self {
-                    $($variant => $ext_type),*
+                    $($variant => $ext_type,)*
+                    $type::Extension { ext_type, .. } => *ext_type,
                 }
             }
 
-            pub fn msg_type(&self) -> u8 {
+            pub const fn msg_type(&self) -> u8 {
                 match self {
-                    $($variant => $msg_type),*
+                    $($variant => $msg_type,)*
+                    $type::Extension { msg_type, .. } => *msg_type,
                 }
             }
 
-            pub fn channel_bit(&self) -> bool {
+            pub const fn channel_bit(&self) -> bool {
                 match self {
-                    $($variant => $channel_bit),*
+                    $($variant => $channel_bit,)*
+                    $type::Extension { channel_bit, .. } => *channel_bit,
                 }
             }
+
+            /// Returns true if the message belongs to a protocol extension.
+            pub const fn is_extension(&self) -> bool {
+                matches!(self, $type::Extension { .. })
+            }
         }
     };
 }
@@ -100,6 +179,61 @@ impl_enum_message_types!(
     MessageType::SetCustomMiningJobError => (0x0000, 0x24, false),
     MessageType::Reconnect => (0x0000, 0x25, false),
     MessageType::SetGroupChannel => (0x0000, 0x26, false),
+    MessageType::AllocateMiningJobToken => (0x0000, 0x50, false),
+    MessageType::AllocateMiningJobTokenSuccess => (0x0000, 0x51, false),
+    MessageType::IdentifyTransactions => (0x0000, 0x53, false),
+    MessageType::IdentifyTransactionsSuccess => (0x0000, 0x54, false),
+    MessageType::ProvideMissingTransactions => (0x0000, 0x55, false),
+    MessageType::ProvideMissingTransactionsSuccess => (0x0000, 0x56, false),
+    MessageType::CommitMiningJob => (0x0000, 0x57, false),
+    MessageType::CommitMiningJobSuccess => (0x0000, 0x58, false),
+    MessageType::CommitMiningJobError => (0x0000, 0x59, false),
+    MessageType::CoinbaseOutputDataSize => (0x0000, 0x70, false),
+    MessageType::NewTemplate => (0x0000, 0x71, false),
+    MessageType::TemplateDistributionSetNewPrevHash => (0x0000, 0x72, false),
+    MessageType::RequestTransactionData => (0x0000, 0x73, false),
+    MessageType::RequestTransactionDataSuccess => (0x0000, 0x74, false),
+    MessageType::RequestTransactionDataError => (0x0000, 0x75, false),
+    MessageType::SubmitSolution => (0x0000, 0x76, false),
     MessageType::TestMessage1 => (0x0000, 0xfe, false),
     MessageType::TestMessage2 => (0x0000, 0xff, true)
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_extension() {
+        let message_type = MessageType::new_extension(0x0001, 0x02, true).unwrap();
+        assert_eq!(message_type.ext_type(), 0x0001);
+        assert_eq!(message_type.msg_type(), 0x02);
+        assert!(message_type.channel_bit());
+        assert!(message_type.is_extension());
+        assert!(!MessageType::SetupConnection.is_extension());
+    }
+
+    #[test]
+    fn new_extension_invalid_ext_type() {
+        assert!(matches!(
+            MessageType::new_extension(0x0000, 0x00, false),
+            Err(Error::RequirementError { .. })
+        ));
+        assert!(matches!(
+            MessageType::new_extension(0x8000, 0x00, false),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+
+    #[test]
+    fn new_unknown_core_message() {
+        assert!(matches!(
+            MessageType::new(0x0000, 0x60),
+            Err(Error::UnknownMessageType())
+        ));
+        assert!(matches!(
+            MessageType::new(0x0001, 0x00),
+            Err(Error::UnknownMessageType())
+        ));
+    }
+}
@@ -2,13 +2,19 @@ mod bytes;
 pub(crate) mod error_code;
 mod fixed;
 pub(crate) mod flags;
+#[cfg(feature = "serde")]
+pub(crate) mod hex;
 mod message_type;
 mod primitives;
+mod sequences;
 mod strings;
+#[cfg(feature = "std")]
 pub mod unix_timestamp;
 
 pub use bytes::{B0_16M, B0_255, B0_31, B0_32, B0_64K};
-pub use fixed::{U24, U256};
+pub use fixed::{ShortTxId, U24, U256};
 pub use message_type::MessageType;
+pub use sequences::{SEQ0_255, SEQ0_64K};
 pub use strings::{STR0_255, STR0_32};
+#[cfg(feature = "std")]
 pub use unix_timestamp::system_unix_time_to_u32;
@@ -1,47 +1,77 @@
 use crate::{
-    codec::{ByteParser, Deserializable, Serializable},
+    codec::{io, ByteParser, Deserializable, Serializable},
     error::Result,
 };
-use std::io;
 
 impl Serializable for bool {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let buffer = if *self { vec![1u8] } else { vec![0u8] };
-        writer.write(&buffer)?;
+        writer.write_all(&buffer)?;
         Ok(buffer.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl Serializable for u8 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let buffer = self.to_le_bytes();
-        writer.write(&buffer)?;
+        writer.write_all(&buffer)?;
         Ok(buffer.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
 }
 
 impl Serializable for u16 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let buffer = self.to_le_bytes();
-        writer.write(&buffer)?;
+        writer.write_all(&buffer)?;
         Ok(buffer.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
 }
 
 impl Serializable for u32 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let buffer = self.to_le_bytes();
-        writer.write(&buffer)?;
+        writer.write_all(&buffer)?;
         Ok(buffer.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
+}
+
+impl Serializable for u64 {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        writer.write_all(&self.to_le_bytes())?;
+        Ok(8)
+    }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
 }
 
 impl Serializable for f32 {
     fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
         let buffer = self.to_le_bytes();
-        writer.write(&buffer)?;
+        writer.write_all(&buffer)?;
         Ok(buffer.len())
     }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
 }
 
 // TODO: The specs states that any bits OUTSIDE of the LSB MUST NOT be interpreted
@@ -86,6 +116,15 @@ impl Deserializable for u32 {
     }
 }
 
+impl Deserializable for u64 {
+    fn deserialize(parser: &mut ByteParser) -> Result<u64> {
+        let mut buffer: [u8; 8] = [0; 8];
+        buffer.clone_from_slice(parser.next_by(8)?);
+
+        Ok(u64::from_le_bytes(buffer))
+    }
+}
+
 impl Deserializable for f32 {
     fn deserialize(parser: &mut ByteParser) -> Result<f32> {
         let mut buffer: [u8; 4] = [0; 4];
@@ -97,7 +136,7 @@ impl Deserializable for f32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::codec::{deserialize, serialize};
+    use crate::codec::{deserialize, serialize, Serializable};
 
     #[test]
     fn u8_serde() {
@@ -129,6 +168,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn u64_serde() {
+        assert_eq!(
+            serialize(&0x0807060504030201u64).unwrap(),
+            vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]
+        );
+        assert_eq!(
+            deserialize::<u64>(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]).unwrap(),
+            0x0807060504030201u64
+        );
+
+        // A short write is an error.
+        let mut buffer = [0u8; 4];
+        assert!(1u64.serialize(&mut &mut buffer[..]).is_err());
+    }
+
     #[test]
     fn f32_serde() {
         // Binary representation of PI in 32-bit floating-point:
@@ -157,4 +212,24 @@ mod tests {
         assert_eq!(deserialize::<bool>(&vec![4u8]).unwrap(), false);
         assert_eq!(deserialize::<bool>(&vec![u8::MAX]).unwrap(), true);
     }
+
+    #[test]
+    fn short_write_err() {
+        let mut buffer = [0u8; 1];
+        assert!(true.serialize(&mut &mut buffer[..0]).is_err());
+        assert!(1u8.serialize(&mut &mut buffer[..0]).is_err());
+        assert!(1u16.serialize(&mut &mut buffer[..]).is_err());
+        assert!(1u32.serialize(&mut &mut buffer[..]).is_err());
+        assert!(1f32.serialize(&mut &mut buffer[..]).is_err());
+    }
+
+    #[test]
+    fn encoded_len() {
+        assert_eq!(true.encoded_len(), serialize(&true).unwrap().len());
+        assert_eq!(1u8.encoded_len(), serialize(&1u8).unwrap().len());
+        assert_eq!(1u16.encoded_len(), serialize(&1u16).unwrap().len());
+        assert_eq!(1u32.encoded_len(), serialize(&1u32).unwrap().len());
+        assert_eq!(1u64.encoded_len(), serialize(&1u64).unwrap().len());
+        assert_eq!(1f32.encoded_len(), serialize(&1f32).unwrap().len());
+    }
 }
@@ -0,0 +1,345 @@
+use crate::codec::{io, ByteParser, Deserializable, Serializable};
+use crate::error::{Error, Result};
+use crate::prelude::*;
+
+/// An internal macro that implements a generic SEQ0 type that is restricted according to a
+/// MAX_LENGTH. The length header counts the number of elements in the sequence, NOT the number
+/// of bytes.
+macro_rules! impl_sized_SEQ0 {
+    ($type:ident, $length_type:ident) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $type<T> {
+            pub length: $length_type,
+            pub data: Vec<T>,
+        }
+
+        impl<T> $type<T> {
+            const MAX_LENGTH: usize = $length_type::MAX as usize;
+
+            /// The constructor enforces the number of elements in the Vec<T> input as the
+            /// MAX_LENGTH. A RequirementError will be returned if the number of elements is
+            /// greater than the MAX_LENGTH.
+            pub fn new<V: Into<Vec<T>>>(value: V) -> Result<$type<T>> {
+                let value = value.into();
+                if value.len() > Self::MAX_LENGTH {
+                    return Err(Error::RequirementError(
+                        "sequence size cannot be greater than MAX_LENGTH".into(),
+                    ));
+                }
+
+                Ok($type {
+                    length: value.len() as $length_type,
+                    data: value,
+                })
+            }
+        }
+
+        /// PartialEq implementation allowing direct comparison between the SEQ0 type and Vec<T>.
+        impl<T: PartialEq> PartialEq<Vec<T>> for $type<T> {
+            fn eq(&self, other: &Vec<T>) -> bool {
+                self.data == *other
+            }
+        }
+
+        /// PartialEq implementation allowing direct comparison between Vec<T> and the SEQ0 type.
+        impl<T: PartialEq> PartialEq<$type<T>> for Vec<T> {
+            fn eq(&self, other: &$type<T>) -> bool {
+                *self == other.data
+            }
+        }
+
+        /// From trait implementation that allows a SEQ0 to be converted into a Vec<T>.
+        impl<T> From<$type<T>> for Vec<T> {
+            fn from(s: $type<T>) -> Self {
+                s.data
+            }
+        }
+
+        /// Deserialize trait implementation that allows a SEQ0 to be deserialized from a
+        /// ByteParser.
+        impl<T: Deserializable> Deserializable for $type<T> {
+            fn deserialize(parser: &mut ByteParser) -> Result<$type<T>> {
+                // Parse the length header before the elements.
+                let header_length = $length_type::deserialize(parser)?;
+
+                // Then parse each element in the sequence. Each element is
+                // at least one byte, so the capacity is capped by the remaining
+                // bytes to prevent a length header from forcing a large
+                // allocation.
+                let capacity = core::cmp::min(header_length as usize, parser.remaining());
+                let mut data = Vec::with_capacity(capacity);
+                for _ in 0..header_length {
+                    data.push(T::deserialize(parser)?);
+                }
+
+                $type::new(data)
+            }
+        }
+
+        /// The serde representation of a SEQ0 is a sequence of its elements.
+        #[cfg(feature = "serde")]
+        impl<T: serde::Serialize> serde::Serialize for $type<T> {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.data.iter())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for $type<T> {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<$type<T>, D::Error> {
+                $type::new(<Vec<T> as serde::Deserialize>::deserialize(deserializer)?)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+
+        /// Serialize trait implementation that allows a SEQ0 to be serialized into an io::Writer.
+        impl<T: Serializable> Serializable for $type<T> {
+            fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+                // Write the length header.
+                let mut length = self.length.serialize(writer)?;
+
+                // Then write each element in the sequence.
+                for element in self.data.iter() {
+                    length += element.serialize(writer)?;
+                }
+
+                Ok(length)
+            }
+
+            fn encoded_len(&self) -> usize {
+                self.length.encoded_len()
+                    + self
+                        .data
+                        .iter()
+                        .map(Serializable::encoded_len)
+                        .sum::<usize>()
+            }
+        }
+    };
+}
+
+impl_sized_SEQ0!(SEQ0_255, u8);
+impl_sized_SEQ0!(SEQ0_64K, u16);
+
+/// OPTION[T] is an alias for SEQ0_1[T]. It has the same representation as a
+/// SEQ0_255 but enforces a maximum size of 1.
+impl<T: Serializable> Serializable for Option<T> {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
+        match self {
+            Some(v) => Ok(1u8.serialize(writer)? + v.serialize(writer)?),
+            None => Ok(0u8.serialize(writer)?),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Some(v) => 1 + v.encoded_len(),
+            None => 1,
+        }
+    }
+}
+
+impl<T: Deserializable> Deserializable for Option<T> {
+    fn deserialize(parser: &mut ByteParser) -> Result<Option<T>> {
+        match u8::deserialize(parser)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::deserialize(parser)?)),
+            _ => Err(Error::RequirementError(
+                "option size cannot be greater than 1".into(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+macro_rules! impl_sized_SEQ0_tests {
+    ($type:ident, $length_type:ident) => {
+        fn make_encoded_seq(elements: &[u16]) -> Vec<u8> {
+            let length = elements.len() as $length_type;
+
+            let mut buffer = vec![];
+            buffer.extend_from_slice(serialize(&length).unwrap().as_slice());
+            for element in elements {
+                buffer.extend_from_slice(&element.to_le_bytes());
+            }
+            return buffer;
+        }
+
+        fn make_decoded_seq(elements: &[u16]) -> $type<u16> {
+            $type {
+                length: elements.len() as $length_type,
+                data: elements.into(),
+            }
+        }
+
+        #[test]
+        fn new_empty() {
+            let data: Vec<u16> = vec![];
+            assert_eq!($type::new(data.clone()).unwrap(), $type { length: 0, data });
+        }
+
+        #[test]
+        fn new_nonempty() {
+            let data: Vec<u16> = vec![1, 2, 3, 4, 5];
+            assert_eq!($type::new(data.clone()).unwrap(), $type { length: 5, data });
+        }
+
+        #[test]
+        fn new_max_length() {
+            let data = vec![0u16; $length_type::MAX as usize];
+            assert_eq!(
+                $type::new(data.clone()).unwrap(),
+                $type {
+                    length: $length_type::MAX,
+                    data
+                }
+            );
+        }
+
+        #[test]
+        fn new_over_limit() {
+            let data = vec![0u16; $length_type::MAX as usize + 1];
+            assert!(matches!(
+                $type::new(data),
+                Err(Error::RequirementError { .. })
+            ));
+        }
+
+        #[test]
+        fn vec_equality() {
+            let data: Vec<u16> = vec![1, 2, 3];
+            let seq = $type::new(data.clone()).unwrap();
+            assert_eq!(seq, data);
+            assert_eq!(data, seq);
+            assert_eq!(Vec::from(seq), data);
+        }
+
+        #[test]
+        fn serde_ok_empty() {
+            let encoded = make_encoded_seq(&[]);
+            let decoded = make_decoded_seq(&[]);
+            assert_eq!(deserialize::<$type<u16>>(&encoded).unwrap(), decoded);
+            assert_eq!(serialize(&decoded).unwrap(), encoded);
+        }
+
+        #[test]
+        fn serde_ok_nonempty() {
+            let encoded = make_encoded_seq(&[1, 2, 3, 4, 5]);
+            let decoded = make_decoded_seq(&[1, 2, 3, 4, 5]);
+            assert_eq!(deserialize::<$type<u16>>(&encoded).unwrap(), decoded);
+            assert_eq!(serialize(&decoded).unwrap(), encoded);
+            assert_eq!(decoded.encoded_len(), encoded.len());
+        }
+
+        #[test]
+        fn serde_ok_nested() {
+            // Sequences of variable length types encode each element with its own header.
+            let decoded = $type::new(vec![B0_255::new([1u8; 2]).unwrap()]).unwrap();
+            let mut encoded = serialize(&(1 as $length_type)).unwrap();
+            encoded.extend(vec![0x02, 0x01, 0x01]);
+            assert_eq!(deserialize::<$type<B0_255>>(&encoded).unwrap(), decoded);
+            assert_eq!(serialize(&decoded).unwrap(), encoded);
+            assert_eq!(decoded.encoded_len(), encoded.len());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn json_ok() {
+            let decoded = make_decoded_seq(&[1, 2, 3]);
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), "[1,2,3]");
+            assert_eq!(
+                serde_json::from_str::<$type<u16>>("[1,2,3]").unwrap(),
+                decoded
+            );
+
+            let over_limit =
+                serde_json::to_string(&vec![0u16; $length_type::MAX as usize + 1]).unwrap();
+            assert!(serde_json::from_str::<$type<u16>>(&over_limit).is_err());
+        }
+
+        #[test]
+        fn deserialize_err() {
+            // No data to deserialize.
+            assert!(matches!(
+                deserialize::<$type<u16>>(&[]),
+                Err(Error::OutOfBounds { .. })
+            ));
+            // No elements after promised length.
+            let encoded = serialize(&(1 as $length_type)).unwrap();
+            assert!(matches!(
+                deserialize::<$type<u16>>(&encoded),
+                Err(Error::OutOfBounds { .. })
+            ));
+            // Insufficient data for the last promised element.
+            let mut encoded = make_encoded_seq(&[1, 2]);
+            encoded.pop();
+            assert!(matches!(
+                deserialize::<$type<u16>>(&encoded),
+                Err(Error::OutOfBounds { .. })
+            ));
+        }
+    };
+}
+
+#[cfg(test)]
+mod seq0_255_tests {
+    use super::*;
+    use crate::codec::{deserialize, serialize};
+    use crate::types::B0_255;
+
+    impl_sized_SEQ0_tests!(SEQ0_255, u8);
+}
+
+#[cfg(test)]
+mod seq0_64k_tests {
+    use super::*;
+    use crate::codec::{deserialize, serialize};
+    use crate::types::B0_255;
+
+    impl_sized_SEQ0_tests!(SEQ0_64K, u16);
+}
+
+#[cfg(test)]
+mod option_tests {
+    use super::*;
+    use crate::codec::{deserialize, serialize};
+
+    #[test]
+    fn serde_ok_none() {
+        assert_eq!(serialize(&None::<u32>).unwrap(), vec![0x00]);
+        assert_eq!(None::<u32>.encoded_len(), 1);
+        assert_eq!(deserialize::<Option<u32>>(&[0x00]).unwrap(), None);
+    }
+
+    #[test]
+    fn serde_ok_some() {
+        let encoded = vec![0x01, 0x05, 0x00, 0x00, 0x00];
+        assert_eq!(serialize(&Some(5u32)).unwrap(), encoded);
+        assert_eq!(Some(5u32).encoded_len(), encoded.len());
+        assert_eq!(deserialize::<Option<u32>>(&encoded).unwrap(), Some(5u32));
+    }
+
+    #[test]
+    fn deserialize_err() {
+        // No data to deserialize.
+        assert!(matches!(
+            deserialize::<Option<u32>>(&[]),
+            Err(Error::OutOfBounds { .. })
+        ));
+        // No data after promised element.
+        assert!(matches!(
+            deserialize::<Option<u32>>(&[0x01]),
+            Err(Error::OutOfBounds { .. })
+        ));
+        // Length greater than 1.
+        assert!(matches!(
+            deserialize::<Option<u32>>(&[0x02, 0x05, 0x00, 0x00, 0x00]),
+            Err(Error::RequirementError { .. })
+        ));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::codec::{ByteParser, Deserializable, Serializable};
+use crate::codec::{io, ByteParser, Deserializable, Serializable};
 use crate::error::{Error, Result};
-use std::io;
+use crate::prelude::*;
 
 /// An internal macro that implements a STR0 type that is restricted according to a MAX_LENGTH.
 macro_rules! impl_sized_STR0 {
@@ -30,6 +30,23 @@ macro_rules! impl_sized_STR0 {
                     data: value,
                 })
             }
+
+            /// Deserializes the STR0 type from a ByteParser without copying the string.
+            /// The returned str borrows from the bytes of the ByteParser.
+            pub fn deserialize_ref<'a>(parser: &mut ByteParser<'a>) -> Result<&'a str> {
+                // Parse the length header before the buffer.
+                let header_length = u8::deserialize(parser)?;
+
+                // Then parse the byte buffer.
+                let bytes = parser.next_by(header_length.into())?;
+                if bytes.len() > Self::MAX_LENGTH {
+                    return Err(Error::RequirementError(
+                        "string size cannot be greater than MAX_LENGTH".into(),
+                    ));
+                }
+
+                Ok(core::str::from_utf8(bytes)?)
+            }
         }
 
         /// PartialEq implementation allowing direct comparison between the STR0 type and String.
@@ -68,16 +85,41 @@ macro_rules! impl_sized_STR0 {
             }
         }
 
+        /// The serde representation of a STR0 is a string.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.data)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<$type, D::Error> {
+                $type::new(<String as serde::Deserialize>::deserialize(deserializer)?)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+
         /// Serialize trait implementation that allows a STR0 to be serialized into an io::Writer.
         impl Serializable for $type {
             fn serialize<W: io::Write>(&self, writer: &mut W) -> Result<usize> {
                 // Write the length header.
                 let header_length = self.length.serialize(writer)?;
                 // Then write the byte buffer.
-                writer.write(self.data.as_bytes())?;
+                writer.write_all(self.data.as_bytes())?;
 
                 Ok(header_length + self.length as usize)
             }
+
+            fn encoded_len(&self) -> usize {
+                self.length.encoded_len() + self.length as usize
+            }
         }
     };
 }
@@ -149,6 +191,21 @@ macro_rules! impl_sized_STR0_tests {
             let decoded = make_decoded_str("valid data");
             assert_eq!(deserialize::<$type>(&encoded).unwrap(), decoded);
             assert_eq!(serialize(&decoded).unwrap(), encoded);
+            assert_eq!(decoded.encoded_len(), encoded.len());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn json_ok() {
+            let decoded = make_decoded_str("valid data");
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), "\"valid data\"");
+            assert_eq!(
+                serde_json::from_str::<$type>("\"valid data\"").unwrap(),
+                decoded
+            );
+
+            let over_limit = format!("\"{}\"", "a".repeat($max_length + 1));
+            assert!(serde_json::from_str::<$type>(&over_limit).is_err());
         }
 
         #[test]
@@ -156,17 +213,17 @@ macro_rules! impl_sized_STR0_tests {
             // No data to deserialize.
             assert!(matches!(
                 deserialize::<$type>(&[]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
             // No data after promised length.
             assert!(matches!(
                 deserialize::<$type>(&[1u8]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
             // Insufficient data after promised length.
             assert!(matches!(
                 deserialize::<$type>(&[2u8, 42u8]),
-                Err(Error::ParseError { .. })
+                Err(Error::OutOfBounds { .. })
             ));
             // Non-Utf8 data.
             let data: [u8; 7] = [0x06, 0xed, 0xa0, 0x80, 0xed, 0xb0, 0x80];
@@ -175,6 +232,20 @@ macro_rules! impl_sized_STR0_tests {
                 Err(Error::FromUtf8Error { .. })
             ));
         }
+
+        #[test]
+        fn deserialize_ref() {
+            let encoded = make_encoded_str("valid data");
+            let mut parser = ByteParser::new(&encoded, 0);
+            assert_eq!($type::deserialize_ref(&mut parser).unwrap(), "valid data");
+
+            let data: [u8; 7] = [0x06, 0xed, 0xa0, 0x80, 0xed, 0xb0, 0x80];
+            let mut parser = ByteParser::new(&data, 0);
+            assert!(matches!(
+                $type::deserialize_ref(&mut parser),
+                Err(Error::Utf8Error { .. })
+            ));
+        }
     };
 }
 
@@ -0,0 +1,426 @@
+use crate::input::encode_hex;
+use crate::Error;
+use serde::Serialize;
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use stratumv2::codec::{
+    deserialize, AnyMessage, ByteParser, Message, MessageRef, FRAME_HEADER_SIZE,
+};
+use stratumv2::common::Protocol;
+use stratumv2::types::{MessageType, U24};
+use stratumv2::{error, job_distribution, job_negotiation, mining, template_distribution};
+
+/// The header fields of a network frame with their offset and size, used to
+/// locate a truncated frame header.
+const HEADER_FIELDS: [(&str, usize, usize); 3] = [
+    ("extension_type", 0, 2),
+    ("msg_type", 2, 1),
+    ("message_length", 3, 3),
+];
+
+/// FramePrinter decodes each network frame into its typed message and writes
+/// the frame header and the message fields in a readable format.
+///
+/// The flags of SetupConnectionSuccess and SetupConnectionError depend on the
+/// sub protocol of the connection, which is taken from the last decoded
+/// SetupConnection. Until a SetupConnection is decoded, the flags are shown
+/// as a number unless the sub protocol was provided.
+pub struct FramePrinter {
+    protocol: Option<Protocol>,
+}
+
+impl FramePrinter {
+    pub fn new(protocol: Option<Protocol>) -> FramePrinter {
+        FramePrinter { protocol }
+    }
+
+    /// Decodes the network frames in the bytes, printing each frame to `out`
+    /// and each error to `err`. A frame with a payload that fails to decode is
+    /// skipped, but decoding stops at an invalid frame header since the
+    /// following frames can no longer be located. Returns the number of errors.
+    pub fn print<W: Write, E: Write>(
+        &mut self,
+        bytes: &[u8],
+        out: &mut W,
+        err: &mut E,
+    ) -> io::Result<usize> {
+        let mut errors = 0;
+        let mut offset = 0;
+        let mut index = 0;
+
+        while offset < bytes.len() {
+            let message = match read_frame(bytes, offset) {
+                Ok(message) => message,
+                Err(e) => {
+                    writeln!(err, "frame {}: {}", index, e)?;
+                    return Ok(errors + 1);
+                }
+            };
+
+            if index > 0 {
+                writeln!(out)?;
+            }
+
+            if let Err(e) = self.write_frame(index, offset, &message, out)? {
+                writeln!(err, "frame {}: {}", index, e)?;
+                errors += 1;
+            }
+
+            offset += FRAME_HEADER_SIZE + message.payload.len();
+            index += 1;
+        }
+
+        Ok(errors)
+    }
+
+    /// Writes the header of the frame and its decoded message. An error is
+    /// returned if the payload cannot be decoded.
+    fn write_frame<W: Write>(
+        &mut self,
+        index: usize,
+        offset: usize,
+        message: &MessageRef,
+        out: &mut W,
+    ) -> io::Result<Result<(), Error>> {
+        let message_type = message.message_type;
+
+        writeln!(
+            out,
+            "frame {} at offset {} ({} bytes)",
+            index,
+            offset,
+            FRAME_HEADER_SIZE + message.payload.len()
+        )?;
+        writeln!(out, "  message_type: {}", message_type_name(message_type))?;
+        writeln!(out, "  extension_type: {:#06x}", message_type.ext_type())?;
+        writeln!(out, "  msg_type: {:#04x}", message_type.msg_type())?;
+        writeln!(out, "  channel_bit: {}", message_type.channel_bit())?;
+        if let Some(channel_id) = message.channel_id() {
+            writeln!(out, "  channel_id: {}", channel_id)?;
+        }
+        writeln!(out, "  message_length: {}", message.payload.len())?;
+
+        let payload_offset = offset + FRAME_HEADER_SIZE;
+        let decoded = match AnyMessage::decode(&Message::from(*message)) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                return Ok(Err(Error::new(
+                    payload_offset + payload_error_offset(&e).unwrap_or(0),
+                    format!(
+                        "failed to decode the {} byte payload of {}: {}",
+                        message.payload.len(),
+                        message_type_name(message_type),
+                        e
+                    ),
+                )))
+            }
+        };
+
+        if let AnyMessage::SetupConnection(setup_connection) = &decoded {
+            self.protocol = Some(setup_connection.protocol());
+        }
+
+        match decoded {
+            AnyMessage::Extension(m) => writeln!(out, "  payload: {}", encode_hex(&m.payload))?,
+            decoded => {
+                let fields = self.message_fields(&decoded).map_err(io::Error::from)?;
+                let fields = serde_json::to_string_pretty(&fields).map_err(io::Error::from)?;
+                writeln!(out, "  message: {}", fields.replace('\n', "\n  "))?;
+            }
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Returns the fields of the message, with the flags of the setup
+    /// connection responses decoded according to the sub protocol.
+    fn message_fields(&self, message: &AnyMessage) -> serde_json::Result<Value> {
+        match (message, self.protocol) {
+            (AnyMessage::SetupConnectionSuccess(m), Some(Protocol::Mining)) => {
+                sub_protocol_fields::<mining::SetupConnectionSuccess, _>(m)
+            }
+            (AnyMessage::SetupConnectionSuccess(m), Some(Protocol::JobNegotiation)) => {
+                sub_protocol_fields::<job_negotiation::SetupConnectionSuccess, _>(m)
+            }
+            (AnyMessage::SetupConnectionSuccess(m), Some(Protocol::TemplateDistribution)) => {
+                sub_protocol_fields::<template_distribution::SetupConnectionSuccess, _>(m)
+            }
+            (AnyMessage::SetupConnectionSuccess(m), Some(Protocol::JobDistribution)) => {
+                sub_protocol_fields::<job_distribution::SetupConnectionSuccess, _>(m)
+            }
+            (AnyMessage::SetupConnectionError(m), Some(Protocol::Mining)) => {
+                sub_protocol_fields::<mining::SetupConnectionError, _>(m)
+            }
+            (AnyMessage::SetupConnectionError(m), Some(Protocol::JobNegotiation)) => {
+                sub_protocol_fields::<job_negotiation::SetupConnectionError, _>(m)
+            }
+            (AnyMessage::SetupConnectionError(m), Some(Protocol::TemplateDistribution)) => {
+                sub_protocol_fields::<template_distribution::SetupConnectionError, _>(m)
+            }
+            (AnyMessage::SetupConnectionError(m), Some(Protocol::JobDistribution)) => {
+                sub_protocol_fields::<job_distribution::SetupConnectionError, _>(m)
+            }
+            // AnyMessage is represented as a map from the name of the variant
+            // to the message, which is already shown as the message_type.
+            _ => match serde_json::to_value(message)? {
+                Value::Object(map) => Ok(map.into_iter().next().map_or(Value::Null, |(_, v)| v)),
+                value => Ok(value),
+            },
+        }
+    }
+}
+
+/// Returns the fields of a common setup connection response converted into
+/// the message of the sub protocol. The common message is returned if its
+/// flags are not valid in the sub protocol.
+fn sub_protocol_fields<T, M>(message: &M) -> serde_json::Result<Value>
+where
+    T: TryFrom<M> + Serialize,
+    M: Clone + Serialize,
+{
+    match T::try_from(message.clone()) {
+        Ok(m) => serde_json::to_value(&m),
+        Err(_) => serde_json::to_value(message),
+    }
+}
+
+fn message_type_name(message_type: MessageType) -> String {
+    match message_type {
+        MessageType::Extension { .. } => "Extension".into(),
+        message_type => format!("{:?}", message_type),
+    }
+}
+
+/// Returns the offset in the payload where decoding failed. Errors of nested
+/// fields are located by the innermost field.
+fn payload_error_offset(e: &error::Error) -> Option<usize> {
+    match e {
+        error::Error::FieldError(_, offset, e) => payload_error_offset(e).or(Some(*offset)),
+        error::Error::OutOfBounds(offset, _, _) => Some(*offset),
+        error::Error::TrailingBytes(offset, _) => Some(*offset),
+        _ => None,
+    }
+}
+
+/// Reads the network frame at the offset. Errors are located by the offset of
+/// the header field that is truncated or invalid.
+fn read_frame(bytes: &[u8], offset: usize) -> Result<MessageRef<'_>, Error> {
+    let remaining = bytes.len() - offset;
+
+    for (name, field_offset, size) in HEADER_FIELDS.iter() {
+        if remaining < field_offset + size {
+            return Err(Error::new(
+                offset + field_offset,
+                format!(
+                    "truncated frame header: {} requires {} bytes but {} remain",
+                    name,
+                    size,
+                    remaining - field_offset
+                ),
+            ));
+        }
+    }
+
+    let message_length = deserialize::<U24>(&bytes[offset + 3..offset + FRAME_HEADER_SIZE])
+        .map_err(|e| Error::new(offset + 3, e.to_string()))?;
+    if remaining < FRAME_HEADER_SIZE + message_length.0 as usize {
+        return Err(Error::new(
+            offset + FRAME_HEADER_SIZE,
+            format!(
+                "truncated payload: message_length is {} bytes but {} remain",
+                message_length.0,
+                remaining - FRAME_HEADER_SIZE
+            ),
+        ));
+    }
+
+    let mut parser = ByteParser::new(bytes, offset);
+    MessageRef::deserialize(&mut parser).map_err(|e| {
+        let field_offset = match e {
+            // The channel bit is the MSB of the little endian extension_type.
+            error::Error::UnexpectedChannelBit(_) => 1,
+            error::Error::UnknownMessageType() => 2,
+            _ => 0,
+        };
+
+        Error::new(
+            offset + field_offset,
+            format!("invalid frame header: {}", e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stratumv2::codec::{frame, serialize};
+    use stratumv2::common::{SetupConnection, SetupConnectionErrorCode};
+
+    fn decode(bytes: &[u8], protocol: Option<Protocol>) -> (String, String, usize) {
+        let mut out = vec![];
+        let mut err = vec![];
+        let errors = FramePrinter::new(protocol)
+            .print(bytes, &mut out, &mut err)
+            .unwrap();
+
+        (
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(err).unwrap(),
+            errors,
+        )
+    }
+
+    fn make_setup_connection_error() -> Vec<u8> {
+        let message = stratumv2::common::SetupConnectionError::new(
+            0x05,
+            SetupConnectionErrorCode::UnsupportedFeatureFlags,
+        )
+        .unwrap();
+
+        serialize(&frame(&message).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn decode_frame() {
+        let message = mining::CloseChannel::new(1, "shutdown").unwrap();
+        let bytes = serialize(&frame(&message).unwrap()).unwrap();
+
+        let (out, err, errors) = decode(&bytes, None);
+        assert_eq!(
+            out,
+            r#"frame 0 at offset 0 (19 bytes)
+  message_type: CloseChannel
+  extension_type: 0x0000
+  msg_type: 0x18
+  channel_bit: true
+  channel_id: 1
+  message_length: 13
+  message: {
+    "channel_id": 1,
+    "reason_code": "shutdown"
+  }
+"#
+        );
+        assert_eq!(err, "");
+        assert_eq!(errors, 0);
+    }
+
+    #[test]
+    fn decode_extension() {
+        let message = Message::new(
+            MessageType::new_extension(0x0001, 0x02, false).unwrap(),
+            vec![0xab, 0xcd],
+        );
+        let bytes = serialize(&message).unwrap();
+
+        let (out, _, errors) = decode(&bytes, None);
+        assert!(out.contains("  message_type: Extension\n  extension_type: 0x0001\n"));
+        assert!(out.contains("  payload: abcd\n"));
+        assert_eq!(errors, 0);
+    }
+
+    #[test]
+    fn decode_setup_connection_flags() {
+        // Without the sub protocol the flags are shown as a number.
+        let (out, _, _) = decode(&make_setup_connection_error(), None);
+        assert!(out.contains(r#""flags": 5,"#));
+
+        let (out, _, _) = decode(&make_setup_connection_error(), Some(Protocol::Mining));
+        assert!(out.contains(
+            r#""flags": [
+      "REQUIRES_STANDARD_JOBS",
+      "REQUIRES_VERSION_ROLLING"
+    ],"#
+        ));
+
+        // The sub protocol is taken from a decoded SetupConnection.
+        let setup_connection = SetupConnection::new_job_negotation(
+            2,
+            2,
+            job_negotiation::SetupConnectionFlags::REQUIRES_ASYNC_JOB_MINING,
+            "0.0.0.0",
+            8545,
+            "Bitmain",
+            "S9i 13.5",
+            "braiins-os-2018-09-22-1-hash",
+            "some-device-uuid",
+        )
+        .unwrap();
+        let mut bytes = serialize(&frame(&setup_connection).unwrap()).unwrap();
+        bytes.extend(make_setup_connection_error());
+
+        // The flags are not valid for the sub protocol.
+        let (out, _, errors) = decode(&bytes, Some(Protocol::Mining));
+        assert!(out.contains(r#""flags": 5,"#));
+        assert_eq!(errors, 0);
+    }
+
+    #[test]
+    fn decode_payload_err() {
+        // The second frame has an invalid payload, but the third frame can
+        // still be decoded.
+        let message =
+            serialize(&frame(&mining::CloseChannel::new(1, "").unwrap()).unwrap()).unwrap();
+        let mut bytes = message.clone();
+        bytes.extend(vec![0x00, 0x80, 0x18, 0x01, 0x00, 0x00, 0x01]);
+        bytes.extend(&message);
+
+        let (out, err, errors) = decode(&bytes, None);
+        assert!(out.contains("frame 2 at offset 18 (11 bytes)"));
+        assert!(err.starts_with(
+            "frame 1: error at byte offset 17: failed to decode the 1 byte payload of CloseChannel:"
+        ));
+        assert_eq!(errors, 1);
+
+        // The reason_code ends before its promised length.
+        let mut bytes = vec![0x00, 0x80, 0x18, 0x06, 0x00, 0x00];
+        bytes.extend(vec![0x01, 0x00, 0x00, 0x00, 0x02, 0x61]);
+        let (_, err, _) = decode(&bytes, None);
+        assert_eq!(
+            err,
+            "frame 0: error at byte offset 11: failed to decode the 6 byte payload of CloseChannel: \
+             failed to deserialize `reason_code` at offset `4`: \
+             expected `2` bytes at offset `5` but only `1` remain\n"
+        );
+
+        // The payload has a trailing byte.
+        let mut bytes = message;
+        bytes[3] += 1;
+        bytes.push(0xff);
+        let (_, err, _) = decode(&bytes, None);
+        assert!(err.starts_with("frame 0: error at byte offset 11:"));
+    }
+
+    #[test]
+    fn decode_header_err() {
+        let message =
+            serialize(&frame(&mining::CloseChannel::new(1, "").unwrap()).unwrap()).unwrap();
+
+        // Truncated message_length.
+        let mut bytes = message.clone();
+        bytes.extend(vec![0x00, 0x80, 0x18, 0x01]);
+        let (out, err, errors) = decode(&bytes, None);
+        assert!(out.contains("frame 0"));
+        assert_eq!(
+            err,
+            "frame 1: error at byte offset 14: truncated frame header: message_length requires 3 bytes but 1 remain\n"
+        );
+        assert_eq!(errors, 1);
+
+        // Truncated payload.
+        let (_, err, _) = decode(&message[..message.len() - 1], None);
+        assert_eq!(
+            err,
+            "frame 0: error at byte offset 6: truncated payload: message_length is 5 bytes but 4 remain\n"
+        );
+
+        // Unknown message type.
+        let (_, err, _) = decode(&[0x00, 0x00, 0xfd, 0x00, 0x00, 0x00], None);
+        assert!(err.starts_with("frame 0: error at byte offset 2: invalid frame header:"));
+
+        // The channel bit is not set for a message with a channel_id.
+        let (_, err, _) = decode(&[0x00, 0x00, 0x18, 0x00, 0x00, 0x00], None);
+        assert!(err.starts_with("frame 0: error at byte offset 1: invalid frame header:"));
+    }
+}
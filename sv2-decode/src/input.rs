@@ -0,0 +1,108 @@
+use crate::Error;
+
+/// The encoding of the bytes read by sv2-decode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// The input is decoded as hex if it only contains hex digits and
+    /// whitespace, otherwise it is read as raw bytes.
+    Auto,
+
+    /// The input is hex encoded. Whitespace between the hex digits is ignored,
+    /// so hex dumps split across lines can be decoded.
+    Hex,
+
+    /// The input is the raw bytes of the network frames.
+    Raw,
+}
+
+/// Returns the bytes of the network frames in the input. Errors in hex
+/// encoded input are located by the offset of the character in the input.
+pub fn read_bytes(input: &[u8], format: Format) -> Result<Vec<u8>, Error> {
+    match format {
+        Format::Hex => decode_hex(input),
+        Format::Raw => Ok(input.to_vec()),
+        Format::Auto if is_hex(input) => decode_hex(input),
+        Format::Auto => Ok(input.to_vec()),
+    }
+}
+
+fn is_hex(input: &[u8]) -> bool {
+    input.iter().any(u8::is_ascii_hexdigit)
+        && input
+            .iter()
+            .all(|c| c.is_ascii_hexdigit() || c.is_ascii_whitespace())
+}
+
+fn decode_hex(input: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(input.len() / 2);
+
+    // The offset and value of the first digit of the byte being decoded.
+    let mut high: Option<(usize, u8)> = None;
+
+    for (offset, c) in input.iter().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+
+        let nibble = (*c as char)
+            .to_digit(16)
+            .ok_or_else(|| Error::new(offset, format!("invalid hex character {:?}", *c as char)))?
+            as u8;
+
+        match high.take() {
+            Some((_, h)) => bytes.push(h << 4 | nibble),
+            None => high = Some((offset, nibble)),
+        }
+    }
+
+    if let Some((offset, _)) = high {
+        return Err(Error::new(offset, "odd number of hex digits"));
+    }
+
+    Ok(bytes)
+}
+
+/// Encodes the bytes as a lowercase hex string.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_hex() {
+        let expected = vec![0x00, 0x80, 0x15, 0xff];
+
+        assert_eq!(read_bytes(b"008015ff", Format::Hex).unwrap(), expected);
+        assert_eq!(read_bytes(b"00 80\n15FF\n", Format::Hex).unwrap(), expected);
+        assert_eq!(
+            read_bytes(b"00 80\n15FF\n", Format::Auto).unwrap(),
+            expected
+        );
+        assert_eq!(encode_hex(&expected), "008015ff");
+    }
+
+    #[test]
+    fn read_raw() {
+        let input = vec![0x00, 0x80, 0x15, 0xff];
+        assert_eq!(read_bytes(&input, Format::Raw).unwrap(), input);
+        assert_eq!(read_bytes(&input, Format::Auto).unwrap(), input);
+
+        // Raw input is never decoded as hex.
+        assert_eq!(read_bytes(b"00", Format::Raw).unwrap(), b"00".to_vec());
+    }
+
+    #[test]
+    fn read_hex_err() {
+        assert_eq!(
+            read_bytes(b"00 8x", Format::Hex),
+            Err(Error::new(4, "invalid hex character 'x'"))
+        );
+        assert_eq!(
+            read_bytes(b"00 801", Format::Hex),
+            Err(Error::new(5, "odd number of hex digits"))
+        );
+    }
+}
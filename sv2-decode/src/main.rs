@@ -0,0 +1,152 @@
+//! sv2-decode reads Stratum V2 network frames as hex or raw bytes from a file
+//! or stdin, and prints the header of each frame and its decoded message.
+//!
+//! ```text
+//! $ echo "008018 0d0000 01000000 08 73687574646f776e" | sv2-decode
+//! frame 0 at offset 0 (19 bytes)
+//!   message_type: CloseChannel
+//!   ...
+//! ```
+mod decode;
+mod input;
+
+use decode::FramePrinter;
+use input::{read_bytes, Format};
+use std::io::{self, Read};
+use std::{env, fmt, fs, process};
+use stratumv2::common::Protocol;
+
+const USAGE: &str = "usage: sv2-decode [--hex | --raw] [--protocol <protocol>] [FILE]
+
+Decodes the Stratum V2 network frames in FILE, or stdin if FILE is omitted or -.
+
+options:
+  --hex                  the input is hex encoded, whitespace is ignored
+  --raw                  the input is raw bytes
+  --protocol <protocol>  the sub protocol used to decode the flags of
+                         SetupConnectionSuccess and SetupConnectionError when
+                         the SetupConnection is not part of the input: mining,
+                         job-negotiation, template-distribution or job-distribution
+  -h, --help             print this message
+
+The input is decoded as hex if neither --hex or --raw is given and it only
+contains hex digits and whitespace.";
+
+/// An error located by the byte offset where decoding failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub offset: usize,
+    pub reason: String,
+}
+
+impl Error {
+    pub fn new<T: Into<String>>(offset: usize, reason: T) -> Error {
+        Error {
+            offset,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error at byte offset {}: {}", self.offset, self.reason)
+    }
+}
+
+/// The command line arguments.
+struct Args {
+    format: Format,
+    protocol: Option<Protocol>,
+    path: Option<String>,
+}
+
+fn parse_protocol(name: &str) -> Option<Protocol> {
+    match name {
+        "mining" => Some(Protocol::Mining),
+        "job-negotiation" => Some(Protocol::JobNegotiation),
+        "template-distribution" => Some(Protocol::TemplateDistribution),
+        "job-distribution" => Some(Protocol::JobDistribution),
+        _ => None,
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let mut parsed = Args {
+        format: Format::Auto,
+        protocol: None,
+        path: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hex" => parsed.format = Format::Hex,
+            "--raw" => parsed.format = Format::Raw,
+            "--protocol" => {
+                let name = args.next().ok_or("--protocol requires a value")?;
+                parsed.protocol =
+                    Some(parse_protocol(&name).ok_or(format!("unknown protocol {:?}", name))?);
+            }
+            "-" if parsed.path.is_none() => (),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {:?}", arg)),
+            _ if parsed.path.is_none() => parsed.path = Some(arg),
+            _ => return Err(format!("unexpected argument {:?}", arg)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn read_input(path: &Option<String>) -> io::Result<Vec<u8>> {
+    match path {
+        Some(path) => fs::read(path),
+        None => {
+            let mut input = vec![];
+            io::stdin().read_to_end(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let args = match parse_args(args.into_iter()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let input = match read_input(&args.path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("failed to read input: {}", e);
+            process::exit(2);
+        }
+    };
+
+    let bytes = match read_bytes(&input, args.format) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let errors = FramePrinter::new(args.protocol)
+        .print(&bytes, &mut io::stdout().lock(), &mut io::stderr().lock())
+        .unwrap_or_else(|e| {
+            eprintln!("failed to write output: {}", e);
+            process::exit(2);
+        });
+
+    if errors > 0 {
+        process::exit(1);
+    }
+}